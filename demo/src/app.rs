@@ -1,22 +1,23 @@
 use eframe::{App, CreationContext, Frame};
 use egui::{
-    global_theme_preference_switch, Align, Button, CentralPanel, Context, Layout, SelectableLabel,
-    Slider, ThemePreference, Ui,
+    global_theme_preference_switch, Align, Button, CentralPanel, ComboBox, Context, Layout,
+    SelectableLabel, Slider, StrokeKind, ThemePreference, Ui,
 };
 use egui_extras::Column;
 use egui_selectable_table::{
-    AutoScroll, ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable, SortOrder,
+    AutoScroll, ColumnOperations, ColumnOrdering, CopyFormat, ReloadPolicy, SelectableRow,
+    SelectableTable, SortOrder,
 };
+use std::time::Duration;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 #[derive(Default, Clone, Copy)]
-pub struct Config {
-    counting_ongoing: bool,
-}
+pub struct Config {}
 
 pub struct MainWindow {
     select_entire_row: bool,
+    incremental_sort: bool,
     add_rows: bool,
     auto_scrolling: bool,
     row_to_add: u64,
@@ -24,8 +25,12 @@ pub struct MainWindow {
     row_count: u64,
     scroll_speed: f32,
     reload_counter: u32,
+    search_text: String,
+    copy_format: CopyFormat,
+    copy_headers: bool,
+    last_double_clicked: Option<i64>,
+    last_paste_error: Option<String>,
     table: SelectableTable<TableRow, TableColumns, Config>,
-    conf: Config,
 }
 
 impl MainWindow {
@@ -39,10 +44,19 @@ impl MainWindow {
         let table = SelectableTable::new(all_columns)
             .auto_reload(10_000)
             .auto_scroll()
-            .horizontal_scroll();
+            .horizontal_scroll()
+            .column_visibility_menu()
+            .column_reordering()
+            .frozen_columns(1)
+            .allow_paste()
+            .editable()
+            .undo_history(100)
+            .async_rebuild()
+            .rebuild_spinner();
 
         MainWindow {
             select_entire_row: false,
+            incremental_sort: false,
             add_rows: false,
             auto_scrolling: true,
             row_to_add: 0,
@@ -50,8 +64,12 @@ impl MainWindow {
             row_count: 0,
             scroll_speed: 30.0,
             reload_counter: 0,
+            search_text: String::new(),
+            copy_format: CopyFormat::default(),
+            copy_headers: false,
+            last_double_clicked: None,
+            last_paste_error: None,
             table,
-            conf: Config::default(),
         }
     }
 }
@@ -73,8 +91,10 @@ impl App for MainWindow {
 
                     // Clear previously added rows
                     self.table.clear_all_rows();
+                    // Reload on the counter, or at least every second while rows are coming
                     self.table.set_auto_reload(Some(self.reload_counter));
-                    self.conf.counting_ongoing = true;
+                    self.table
+                        .set_reload_policy(Some(ReloadPolicy::Interval(Duration::from_secs(1))));
                 };
                 ui.separator();
                 if ui
@@ -83,6 +103,16 @@ impl App for MainWindow {
                 {
                     self.table.set_select_full_row(self.select_entire_row);
                 };
+                ui.separator();
+                if ui
+                    .checkbox(
+                        &mut self.incremental_sort,
+                        "Keep rows sorted as they change?",
+                    )
+                    .changed()
+                {
+                    self.table.set_incremental_sort(self.incremental_sort);
+                };
             });
             ui.separator();
             ui.horizontal(|ui| {
@@ -110,6 +140,57 @@ impl App for MainWindow {
                 ui.label("Higher value = Less often the UI is refreshed")
             });
             ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Copy format:");
+                let previous_format = self.copy_format;
+                ComboBox::from_id_salt("copy_format")
+                    .selected_text(format!("{:?}", self.copy_format))
+                    .show_ui(ui, |ui| {
+                        for format in [
+                            CopyFormat::Aligned,
+                            CopyFormat::Tsv,
+                            CopyFormat::Csv,
+                            CopyFormat::Markdown,
+                            CopyFormat::Html,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.copy_format,
+                                format,
+                                format!("{format:?}"),
+                            );
+                        }
+                    });
+                if previous_format != self.copy_format {
+                    self.table.set_copy_format(self.copy_format);
+                }
+                if ui
+                    .checkbox(&mut self.copy_headers, "Include header names?")
+                    .changed()
+                {
+                    self.table.set_copy_headers(self.copy_headers);
+                }
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                if ui.text_edit_singleline(&mut self.search_text).changed() {
+                    self.table.set_search(&self.search_text);
+                }
+                ui.label(format!(
+                    "Showing {} of {} rows",
+                    self.table.total_displayed_rows(),
+                    self.table.total_rows()
+                ));
+                if let Some(row_id) = self.last_double_clicked {
+                    ui.separator();
+                    ui.label(format!("Last double-clicked row ID: {row_id}"));
+                }
+                if let Some(error) = &self.last_paste_error {
+                    ui.separator();
+                    ui.label(format!("Paste failed: {error}"));
+                }
+            });
+            ui.separator();
 
             if self.row_count * 10 / 100 > self.reload_counter as u64 {
                 ui.horizontal(|ui| {
@@ -120,22 +201,23 @@ impl App for MainWindow {
                 ui.separator();
             }
 
-            self.table.show_ui(ui, |table| {
-                let mut table = table
+            // Columns are added by the table through `column_layout` so hidden columns are skipped
+            let response = self.table.show_ui_frozen(ui, |table| {
+                table
                     .drag_to_scroll(false)
                     .striped(true)
                     .resizable(true)
                     .cell_layout(Layout::left_to_right(Align::Center))
                     .drag_to_scroll(false)
                     .auto_shrink([false; 2])
-                    .min_scrolled_height(0.0);
-
-                for _col in TableColumns::iter() {
-                    table = table.column(Column::initial(150.0))
-                }
-                table
+                    .min_scrolled_height(0.0)
             });
-            self.table.set_config(self.conf);
+            if let Some((row_id, _)) = response.double_clicked_cell() {
+                self.last_double_clicked = Some(row_id);
+            }
+            if let Some((row_id, _, error)) = response.paste_failures().first() {
+                self.last_paste_error = Some(format!("Row ID {row_id}: {error}"));
+            }
 
             if self.add_rows {
                 for _num in 0..10000 {
@@ -147,7 +229,7 @@ impl App for MainWindow {
                             field_4: format!("field 4 with row num: {}", self.row_num),
                             field_5: format!("field 5 with row num: {}", self.row_num),
                             field_6: format!("field 6 with row num: {}", self.row_num),
-                            create_count: 0,
+                            click_count: 0,
                         };
                         Some(new_row)
                     });
@@ -158,8 +240,8 @@ impl App for MainWindow {
                         self.row_num = 0;
                         // forcefully reload the table as there are no more rows coming
                         self.table.recreate_rows();
-                        self.conf.counting_ongoing = false;
                         self.table.set_auto_reload(None);
+                        self.table.set_reload_policy(None);
 
                         break;
                     }
@@ -179,7 +261,7 @@ struct TableRow {
     field_4: String,
     field_5: String,
     field_6: String,
-    create_count: u64,
+    click_count: u64,
 }
 
 #[derive(Eq, PartialEq, Debug, Ord, PartialOrd, Clone, Copy, Hash, Default, EnumIter)]
@@ -203,26 +285,58 @@ impl ColumnOperations<TableRow, TableColumns, Config> for TableColumns {
             TableColumns::Field4 => row.field_4.to_string(),
             TableColumns::Field5 => row.field_5.to_string(),
             TableColumns::Field6 => row.field_6.to_string(),
-            TableColumns::Field7 => row.create_count.to_string(),
+            TableColumns::Field7 => row.click_count.to_string(),
         }
     }
-    fn create_header(
-        &self,
-        ui: &mut Ui,
-        sort_order: Option<SortOrder>,
-        _table: &mut SelectableTable<TableRow, TableColumns, Config>,
-    ) -> Option<egui::Response> {
-        let mut text = match self {
+    fn column_name(&self) -> String {
+        match self {
             TableColumns::Field1 => "Field 1",
             TableColumns::Field2 => "Field 2",
             TableColumns::Field3 => "Field 3",
             TableColumns::Field4 => "Field 4",
             TableColumns::Field5 => "Field 5",
             TableColumns::Field6 => "Field 6",
-            TableColumns::Field7 => "Row Creation Count",
+            TableColumns::Field7 => "Row Click Count",
+        }
+        .to_string()
+    }
+    fn column_layout(&self) -> Option<Column> {
+        Some(Column::initial(150.0))
+    }
+    fn set_from_text(&self, row: &mut TableRow, text: &str) -> Result<(), String> {
+        match self {
+            TableColumns::Field1 => {
+                row.field_1 = text
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Field 1 value: {e}"))?;
+            }
+            TableColumns::Field2 => {
+                row.field_2 = text
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Field 2 value: {e}"))?;
+            }
+            TableColumns::Field3 => row.field_3 = text.to_string(),
+            TableColumns::Field4 => row.field_4 = text.to_string(),
+            TableColumns::Field5 => row.field_5 = text.to_string(),
+            TableColumns::Field6 => row.field_6 = text.to_string(),
+            TableColumns::Field7 => return Err("Row Click Count cannot be modified".to_string()),
         }
-        .to_string();
-        if let Some(sort) = sort_order {
+        Ok(())
+    }
+    fn create_header(
+        &self,
+        ui: &mut Ui,
+        sort_order: Option<(usize, SortOrder)>,
+        table: &mut SelectableTable<TableRow, TableColumns, Config>,
+    ) -> Option<egui::Response> {
+        let mut text = self.column_name();
+        if let Some((position, sort)) = sort_order {
+            // Only number the keys when sorting by more than one column
+            if table.sort_stack().len() > 1 {
+                text += &format!(" {}", position + 1);
+            }
             match sort {
                 SortOrder::Ascending => text += "🔽",
                 SortOrder::Descending => text += "🔼",
@@ -235,13 +349,13 @@ impl ColumnOperations<TableRow, TableColumns, Config> for TableColumns {
     fn create_table_row(
         &self,
         ui: &mut Ui,
-        row: &SelectableRow<TableRow, TableColumns>,
+        row: &SelectableRow<TableRow>,
         cell_selected: bool,
+        cell_focused: bool,
         table: &mut SelectableTable<TableRow, TableColumns, Config>,
     ) -> egui::Response {
         let row_id = row.id;
         let row_data = &row.row_data;
-        let config = table.config;
 
         let text = match self {
             TableColumns::Field1 => row_data.field_1.to_string(),
@@ -250,29 +364,9 @@ impl ColumnOperations<TableRow, TableColumns, Config> for TableColumns {
             TableColumns::Field4 => row_data.field_4.to_string(),
             TableColumns::Field5 => row_data.field_5.to_string(),
             TableColumns::Field6 => row_data.field_6.to_string(),
-            TableColumns::Field7 => row_data.create_count.to_string(),
+            TableColumns::Field7 => row_data.click_count.to_string(),
         };
 
-        // Persist the creation count, while row creation is ongoing, this will get auto
-        // reloaded. After there is no more row creation, auto reload is turned off and won't
-        // reload until next manual intervention. While no more rows are being created, we are
-        // modifying the rows directly that are being shown in the UI which is much less
-        // expensive and gets shown to the UI immediately
-        // Continue to update the persistent row data to ensure once reload happens, the
-        // previous count data is not lost
-        table.add_modify_row(|table| {
-            let target_row = table.get_mut(&row_id).unwrap();
-            target_row.row_data.create_count += 1;
-            None
-        });
-        if !config.counting_ongoing {
-            table.modify_shown_row(|t, index| {
-                let target_index = index.get(&row_id).unwrap();
-                let target_row = t.get_mut(*target_index).unwrap();
-                target_row.row_data.create_count += 1;
-            });
-        }
-
         // The same approach works for both cell based selection and for entire row selection on
         // drag.
         let resp = ui.add_sized(
@@ -280,6 +374,29 @@ impl ColumnOperations<TableRow, TableColumns, Config> for TableColumns {
             SelectableLabel::new(cell_selected, text),
         );
 
+        // Draw a focus ring around the cell that has the keyboard cursor
+        if cell_focused {
+            ui.painter().rect_stroke(
+                resp.rect,
+                2.0,
+                ui.visuals().selection.stroke,
+                StrokeKind::Inside,
+            );
+        }
+
+        // Persist the click count. The shown rows are the stored rows, so the count is kept when
+        // the rows are reloaded and is not recorded in the undo history. Modifying the row that is
+        // being drawn copies it, so it is only done when the cell is clicked.
+        //
+        // The row may have been removed through the context menu of an earlier cell of this row
+        if resp.clicked() {
+            table.modify_shown_row(|rows, _| {
+                if let Some(target_row) = rows.get_mut(&row_id) {
+                    target_row.row_data.click_count += 1;
+                }
+            });
+        }
+
         resp.context_menu(|ui| {
             if ui.button("Select All Rows").clicked() {
                 table.select_all();
@@ -293,6 +410,24 @@ impl ColumnOperations<TableRow, TableColumns, Config> for TableColumns {
                 table.copy_selected_cells(ui);
                 ui.close_menu();
             }
+            if ui.button("Remove Selected Rows").clicked() {
+                table.remove_selected_rows();
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(table.can_undo(), Button::new("Undo"))
+                .clicked()
+            {
+                table.undo();
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(table.can_redo(), Button::new("Redo"))
+                .clicked()
+            {
+                table.redo();
+                ui.close_menu();
+            }
         });
         resp
     }
@@ -307,7 +442,7 @@ impl ColumnOrdering<TableRow> for TableColumns {
             TableColumns::Field4 => row_1.field_4.cmp(&row_2.field_4),
             TableColumns::Field5 => row_1.field_5.cmp(&row_2.field_5),
             TableColumns::Field6 => row_1.field_6.cmp(&row_2.field_6),
-            TableColumns::Field7 => row_1.create_count.cmp(&row_2.create_count),
+            TableColumns::Field7 => row_1.click_count.cmp(&row_2.click_count),
         }
     }
 }
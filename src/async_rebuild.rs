@@ -0,0 +1,480 @@
+use egui::ahash::HashMap;
+use egui::{pos2, vec2, Context, Rect, Spinner, Ui};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::atomic::{self, AtomicU64};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::filter::RowFilter;
+use crate::search::row_matches_search;
+use crate::{ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable, SortOrder};
+
+/// Everything that decides which rows are displayed and in which order
+pub struct RowQuery<Row, F> {
+    /// The active column filters
+    pub filters: Vec<RowFilter<Row>>,
+    /// The lowercased search query, if any
+    pub search: Option<String>,
+    /// The columns checked by the search query
+    pub searched_columns: Vec<F>,
+    /// The columns to sort by, in order of priority
+    pub sort_stack: Vec<(F, SortOrder)>,
+}
+
+/// Returns the rows that pass the filters and the search query of the query, in no particular order
+pub fn filter_rows<'a, Row, F, Conf>(
+    rows: &'a HashMap<i64, Arc<SelectableRow<Row>>>,
+    query: &RowQuery<Row, F>,
+) -> Vec<&'a SelectableRow<Row>>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    rows.par_iter()
+        .filter(|(_, v)| row_matches_query::<Row, F, Conf>(&v.row_data, query))
+        .map(|(_, v)| v.as_ref())
+        .collect()
+}
+
+/// Whether the row passes the filters and the search query of the query
+pub fn row_matches_query<Row, F, Conf>(row: &Row, query: &RowQuery<Row, F>) -> bool
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    query.filters.iter().all(|filter| filter(row))
+        && query.search.as_ref().is_none_or(|search| {
+            row_matches_search::<Row, F, Conf>(row, search, &query.searched_columns)
+        })
+}
+
+/// Sorts the rows by the columns of the sort stack
+pub fn sort_filtered_rows<Row, F>(rows: &mut [&SelectableRow<Row>], sort_stack: &[(F, SortOrder)])
+where
+    Row: Clone + Send + Sync,
+    F: Eq + Hash + Clone + Ord + Send + Sync + Default + ColumnOrdering<Row>,
+{
+    rows.par_sort_by(|a, b| compare_rows(a, b, sort_stack));
+}
+
+/// Compares the rows by the columns of the sort stack. Rows that are equal in every column are
+/// ordered by their ID so the order never depends on how the rows are stored.
+pub fn compare_rows<Row, F>(
+    a: &SelectableRow<Row>,
+    b: &SelectableRow<Row>,
+    sort_stack: &[(F, SortOrder)],
+) -> Ordering
+where
+    Row: Clone + Send + Sync,
+    F: ColumnOrdering<Row>,
+{
+    sort_stack
+        .iter()
+        .map(|(column, sort_order)| {
+            let ordering = column.order_by(&a.row_data, &b.row_data);
+            match sort_order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.id.cmp(&b.id))
+}
+
+/// A snapshot of the rows to filter and sort away from the UI thread
+pub struct RebuildJob<Row, F>
+where
+    Row: Clone + Send + Sync,
+    F: Eq + Hash + Clone + Ord + Send + Sync + Default,
+{
+    /// The rows at the time the rebuild started, shared with the table until it changes them
+    rows: Arc<HashMap<i64, Arc<SelectableRow<Row>>>>,
+    /// The filters, search and sort order at the time the rebuild started
+    query: RowQuery<Row, F>,
+    /// Identifies the rebuild, only the result of the latest one is used
+    generation: u64,
+}
+
+/// The state shared between the table and the running rebuilds
+#[derive(Default)]
+pub struct RebuildShared {
+    /// The generation of the latest rebuild. Older rebuilds stop as soon as they notice.
+    generation: AtomicU64,
+    /// The result waiting to be picked up by the table
+    state: Mutex<RebuildState>,
+}
+
+/// The finished result and the context to repaint once it is ready
+#[derive(Default)]
+struct RebuildState {
+    /// The context of the UI showing the table, known after the first `show_ui` call
+    ctx: Option<Context>,
+    /// The generation of the finished rebuild and the IDs of the rows to display, in order
+    finished: Option<(u64, Vec<i64>)>,
+}
+
+impl RebuildShared {
+    /// Whether a newer rebuild was started after the given one
+    fn is_stale(&self, generation: u64) -> bool {
+        self.generation.load(atomic::Ordering::Acquire) != generation
+    }
+}
+
+/// Runs the rebuilds on a background thread without the table having to know the `Conf` type
+pub trait RebuildWorker<Row, F>: Send + Sync
+where
+    Row: Clone + Send + Sync,
+    F: Eq + Hash + Clone + Ord + Send + Sync + Default,
+{
+    /// Filter and sort the snapshot in the background and store the result in the shared state
+    fn spawn(&self, job: RebuildJob<Row, F>, shared: Arc<RebuildShared>);
+}
+
+/// Rebuilds the rows on the rayon thread pool
+struct RayonWorker<Conf> {
+    conf: PhantomData<fn() -> Conf>,
+}
+
+impl<Row, F, Conf> RebuildWorker<Row, F> for RayonWorker<Conf>
+where
+    Row: Clone + Send + Sync + 'static,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>
+        + 'static,
+    Conf: Default + 'static,
+{
+    fn spawn(&self, job: RebuildJob<Row, F>, shared: Arc<RebuildShared>) {
+        rayon::spawn(move || {
+            if shared.is_stale(job.generation) {
+                return;
+            }
+            let mut rows = filter_rows::<Row, F, Conf>(&job.rows, &job.query);
+
+            if shared.is_stale(job.generation) {
+                return;
+            }
+            sort_filtered_rows(&mut rows, &job.query.sort_stack);
+
+            let ids = rows.iter().map(|row| row.id).collect();
+
+            let mut state = shared.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if shared.is_stale(job.generation) {
+                return;
+            }
+            state.finished = Some((job.generation, ids));
+            if let Some(ctx) = &state.ctx {
+                ctx.request_repaint();
+            }
+        });
+    }
+}
+
+/// Rebuilds the displayed rows in the background
+pub struct AsyncRebuild<Row, F>
+where
+    Row: Clone + Send + Sync,
+    F: Eq + Hash + Clone + Ord + Send + Sync + Default,
+{
+    /// Runs the rebuilds
+    worker: Box<dyn RebuildWorker<Row, F>>,
+    /// Shared with the running rebuilds
+    shared: Arc<RebuildShared>,
+    /// Whether a rebuild was started and its result was not displayed yet
+    pending: bool,
+    /// The rows added or changed while the rebuild was running. They are placed again once its
+    /// result is displayed, as the rebuild only knows their earlier state.
+    placed_ids: Vec<i64>,
+}
+
+/// Functions related to rebuilding the displayed rows in the background
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync + 'static,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>
+        + 'static,
+    Conf: Default + 'static,
+{
+    /// Filters and sorts the rows on a background thread whenever the rows are recreated, so large
+    /// tables never block the frame. The previous rows stay displayed until the result is ready.
+    /// Starting a new recreation cancels the running one.
+    ///
+    /// # Considerations:
+    /// - The rows are shared with the rebuild, so starting one is `O(1)` on the UI thread. If rows
+    ///   are added, changed or removed while it runs, the map of the rows is copied once, which
+    ///   copies a pointer per row, and each modified row is copied once.
+    /// - Rows added or changed while a rebuild is running, other than with `modify_shown_row`, are
+    ///   placed by their current data when the result is displayed, and removed rows are left
+    ///   out of it.
+    /// - Functions reading the displayed rows right after
+    ///   [`recreate_rows`](#method.recreate_rows) see the previous rows until the rebuild
+    ///   finishes. Use [`is_rebuilding`](#method.is_rebuilding) to check.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table rebuilding the rows in the background.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .async_rebuild()
+    ///     .rebuild_spinner();
+    /// ```
+    #[must_use]
+    pub fn async_rebuild(mut self) -> Self {
+        self.set_async_rebuild(true);
+        self
+    }
+
+    /// Sets whether the rows are filtered and sorted on a background thread. Disabling it while
+    /// a rebuild is running recreates the rows right away.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to rebuild the rows in the background, `false` to rebuild them on the
+    ///   UI thread.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_async_rebuild(row_count > 100_000);
+    /// ```
+    pub fn set_async_rebuild(&mut self, status: bool) {
+        if status == self.async_rebuild.is_some() {
+            return;
+        }
+
+        if status {
+            self.async_rebuild = Some(AsyncRebuild {
+                worker: Box::new(RayonWorker::<Conf> { conf: PhantomData }),
+                shared: Arc::default(),
+                pending: false,
+                placed_ids: Vec::new(),
+            });
+        } else if self
+            .async_rebuild
+            .take()
+            .is_some_and(|rebuild| rebuild.pending)
+        {
+            self.recreate_rows();
+        }
+    }
+}
+
+/// Functions related to rebuilding the displayed rows in the background
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Shows a spinner in the header of the primary sort column while the rows are being rebuilt
+    /// in the background.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table showing the spinner.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .async_rebuild()
+    ///     .rebuild_spinner();
+    /// ```
+    #[must_use]
+    pub const fn rebuild_spinner(mut self) -> Self {
+        self.rebuild_spinner = true;
+        self
+    }
+
+    /// Sets whether a spinner is shown in the header while the rows are being rebuilt in the
+    /// background.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to show the spinner, `false` to hide it.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_rebuild_spinner(true);
+    /// ```
+    pub const fn set_rebuild_spinner(&mut self, status: bool) {
+        self.rebuild_spinner = status;
+    }
+
+    /// Whether the rows are being rebuilt in the background and the previous rows are still
+    /// displayed.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` while a background rebuild is running.
+    pub fn is_rebuilding(&self) -> bool {
+        self.async_rebuild
+            .as_ref()
+            .is_some_and(|rebuild| rebuild.pending)
+    }
+
+    /// The filters, search and sort order deciding the displayed rows
+    pub(crate) fn row_query(&self) -> RowQuery<Row, F> {
+        RowQuery {
+            filters: self.column_filters.values().cloned().collect(),
+            search: self.search_query.as_ref().map(|query| query.to_lowercase()),
+            searched_columns: self.searched_columns(),
+            sort_stack: self.sort_stack.clone(),
+        }
+    }
+
+    /// Start rebuilding the rows in the background, cancelling the running rebuild. Returns
+    /// `false` if background rebuilds are not enabled.
+    pub(crate) fn start_async_rebuild(&mut self) -> bool {
+        if self.async_rebuild.is_none() {
+            return false;
+        }
+
+        // The map of the rows is shared with the rebuild and only copied if it changes meanwhile
+        let rows = Arc::clone(&self.rows);
+        let query = self.row_query();
+
+        let Some(rebuild) = self.async_rebuild.as_mut() else {
+            return false;
+        };
+
+        let generation = rebuild
+            .shared
+            .generation
+            .fetch_add(1, atomic::Ordering::AcqRel)
+            + 1;
+        rebuild.pending = true;
+        rebuild.placed_ids.clear();
+
+        let job = RebuildJob {
+            rows,
+            query,
+            generation,
+        };
+        rebuild.worker.spawn(job, Arc::clone(&rebuild.shared));
+        true
+    }
+
+    /// Display the result of the latest background rebuild once it is ready
+    pub(crate) fn poll_async_rebuild(&mut self, ui: &Ui) {
+        let Some(rebuild) = self.async_rebuild.as_mut() else {
+            return;
+        };
+        if !rebuild.pending {
+            return;
+        }
+
+        let finished = {
+            let mut state = rebuild
+                .shared
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if state.ctx.is_none() {
+                state.ctx = Some(ui.ctx().clone());
+            }
+            state.finished.take()
+        };
+
+        let Some((generation, ids)) = finished else {
+            return;
+        };
+        if rebuild.shared.is_stale(generation) {
+            return;
+        }
+        rebuild.pending = false;
+        let mut placed_ids = std::mem::take(&mut rebuild.placed_ids);
+
+        let selection = self.take_selection();
+        self.formatted_rows = ids
+            .into_iter()
+            .filter(|id| self.rows.contains_key(id))
+            .collect();
+        self.sort_outdated = false;
+        self.restore_selection(selection);
+
+        // The rows changed since the rebuild started are placed by their current data, with or
+        // without the incremental sort, so the result is not older than the call that started it
+        if !placed_ids.is_empty() {
+            placed_ids.sort_unstable();
+            placed_ids.dedup();
+            let query = self.row_query();
+            self.place_rows(&placed_ids, &query);
+        }
+    }
+
+    /// Remember a row added or changed while a rebuild is running so it is placed again in its
+    /// result
+    pub(crate) fn note_placed_row(&mut self, id: i64) {
+        if let Some(rebuild) = self
+            .async_rebuild
+            .as_mut()
+            .filter(|rebuild| rebuild.pending)
+        {
+            rebuild.placed_ids.push(id);
+        }
+    }
+
+    /// Paint the spinner over the right side of the header cell if the column is the one showing
+    /// the rebuild progress
+    pub(crate) fn paint_rebuild_spinner(&self, ui: &mut Ui, column: &F) {
+        if !self.rebuild_spinner || !self.is_rebuilding() {
+            return;
+        }
+
+        let spinner_column = self
+            .sort_stack
+            .first()
+            .map(|(column, _)| column.clone())
+            .filter(|column| self.is_column_visible(column))
+            .or_else(|| self.visible_columns().into_iter().next());
+        if spinner_column.as_ref() != Some(column) {
+            return;
+        }
+
+        let rect = ui.max_rect();
+        let size = rect.height().min(ui.spacing().interact_size.y);
+        let spinner_rect = Rect::from_min_size(
+            pos2(rect.right() - size, rect.center().y - size / 2.0),
+            vec2(size, size),
+        );
+        ui.put(spinner_rect, Spinner::new().size(size * 0.8));
+    }
+}
@@ -1,26 +1,129 @@
+use egui::Ui;
 use std::hash::Hash;
+use std::time::Duration;
 
 use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
 
+/// Decides when the displayed rows are recreated automatically after rows are added or modified.
+/// No policy reloads without pending changes.
+///
+/// # Example:
+/// ```rust,ignore
+/// // Reload after 10k changes, or once the changes stopped for half a second
+/// let policy = ReloadPolicy::Any(vec![
+///     ReloadPolicy::EveryChanges(10_000),
+///     ReloadPolicy::Debounce(Duration::from_millis(500)),
+/// ]);
+///
+/// // Reload after 10k changes, but at most once per second
+/// let throttled = ReloadPolicy::All(vec![
+///     ReloadPolicy::EveryChanges(10_000),
+///     ReloadPolicy::Interval(Duration::from_secs(1)),
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReloadPolicy {
+    /// Reload as soon as the number of added or modified rows reaches the count
+    EveryChanges(u32),
+    /// Reload at most once per duration while there are changes
+    Interval(Duration),
+    /// Reload once there were no changes for the duration
+    Debounce(Duration),
+    /// Reload as soon as any of the policies would
+    Any(Vec<Self>),
+    /// Reload only once all of the policies would, such as a count throttled by an interval
+    All(Vec<Self>),
+}
+
+impl ReloadPolicy {
+    /// Whether the pending changes should be reloaded now
+    fn is_due(&self, state: &AutoReload) -> bool {
+        match self {
+            Self::EveryChanges(count) => state.reload_count >= *count,
+            Self::Interval(interval) => state.now - state.last_reload >= interval.as_secs_f64(),
+            Self::Debounce(quiet) => state.now - state.last_change >= quiet.as_secs_f64(),
+            Self::Any(policies) => policies.iter().any(|policy| policy.is_due(state)),
+            Self::All(policies) => policies.iter().all(|policy| policy.is_due(state)),
+        }
+    }
+
+    /// The seconds left until the policy is due if only time passes, negative if overdue. `None`
+    /// if it is only due after more changes.
+    fn seconds_left(&self, state: &AutoReload) -> Option<f64> {
+        match self {
+            Self::EveryChanges(count) => (state.reload_count >= *count).then_some(0.0),
+            Self::Interval(interval) => {
+                Some(state.last_reload + interval.as_secs_f64() - state.now)
+            }
+            Self::Debounce(quiet) => Some(state.last_change + quiet.as_secs_f64() - state.now),
+            Self::Any(policies) => policies
+                .iter()
+                .filter_map(|policy| policy.seconds_left(state))
+                .reduce(f64::min),
+            Self::All(policies) => policies
+                .iter()
+                .map(|policy| policy.seconds_left(state))
+                .try_fold(0.0, |longest: f64, left| Some(longest.max(left?))),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct AutoReload {
     pub reload_after: Option<u32>,
     pub reload_count: u32,
+    /// When to reload in addition to `reload_after`, never if `None`
+    policy: Option<ReloadPolicy>,
+    /// The time of the current frame, in seconds
+    now: f64,
+    /// The frame time of the last change
+    last_change: f64,
+    /// The frame time when the rows were last recreated
+    last_reload: f64,
 }
 impl AutoReload {
     /// Increase the current reload count and return bool based on if it is equal or above the count it is
-    /// supposed to reload at
+    /// supposed to reload at or the policy wants the rows to be recreated right away
     pub(crate) fn increment_count(&mut self) -> bool {
         self.reload_count += 1;
-        if let Some(count) = self.reload_after {
-            let reload = self.reload_count >= count;
-            if reload {
-                self.reload_count = 0;
-            }
-            reload
-        } else {
-            false
+        self.last_change = self.now;
+        self.is_due()
+    }
+
+    /// Whether there are changes and either the count or the policy wants them reloaded now
+    fn is_due(&self) -> bool {
+        if self.reload_count == 0 {
+            return false;
+        }
+        self.reload_after
+            .is_some_and(|count| self.reload_count >= count)
+            || self
+                .policy
+                .as_ref()
+                .is_some_and(|policy| policy.is_due(self))
+    }
+
+    /// Forget the counted changes as the rows were recreated
+    pub(crate) const fn mark_reloaded(&mut self) {
+        self.reload_count = 0;
+        self.last_reload = self.now;
+    }
+
+    /// Set the policy and start counting again
+    fn set_policy(&mut self, policy: Option<ReloadPolicy>) {
+        self.policy = policy;
+        self.reload_count = 0;
+        self.last_change = self.now;
+        self.last_reload = self.now;
+    }
+
+    /// The seconds left until the pending changes should be reloaded, if a time based policy is
+    /// set and there are changes
+    fn seconds_left(&self) -> Option<f64> {
+        if self.reload_count == 0 {
+            return None;
         }
+        self.policy.as_ref()?.seconds_left(self)
     }
 }
 
@@ -81,4 +184,73 @@ where
         self.auto_reload.reload_after = count;
         self.auto_reload.reload_count = 0;
     }
+
+    /// Recreate the rows automatically based on the number of changes, the time since the last
+    /// recreation or the time since the last change. See [`ReloadPolicy`].
+    ///
+    /// # Considerations:
+    /// - The time based policies are checked on every `show_ui` call using the frame time of egui,
+    ///   and a repaint is requested for when the next reload is due.
+    /// - Changes are counted the same way as with [`auto_reload`](#method.auto_reload). Any
+    ///   recreation of the rows, including calling [`recreate_rows`](#method.recreate_rows), starts
+    ///   counting again.
+    /// - The [`auto_reload`](#method.auto_reload) count still reloads on its own. To throttle the
+    ///   count with [`ReloadPolicy::All`], disable it with `set_auto_reload(None)`.
+    ///
+    /// # Parameters:
+    /// - `policy`: When to recreate the rows.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the reload policy.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// // Show new rows at least every second, even if only a few arrive
+    /// let table = SelectableTable::new(vec![col1, col2, col3]).reload_policy(ReloadPolicy::Any(vec![
+    ///     ReloadPolicy::EveryChanges(5000),
+    ///     ReloadPolicy::Interval(Duration::from_secs(1)),
+    /// ]));
+    /// ```
+    #[must_use]
+    pub fn reload_policy(mut self, policy: ReloadPolicy) -> Self {
+        self.auto_reload.set_policy(Some(policy));
+        self
+    }
+
+    /// Sets when the rows are recreated automatically, replacing any previous policy. The
+    /// [`auto_reload`](#method.auto_reload) count is kept and reloads on its own.
+    ///
+    /// # Parameters:
+    /// - `policy`: When to recreate the rows, or `None` to only reload on the `auto_reload` count.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_reload_policy(Some(ReloadPolicy::Debounce(Duration::from_millis(300))));
+    /// ```
+    pub fn set_reload_policy(&mut self, policy: Option<ReloadPolicy>) {
+        self.auto_reload.set_policy(policy);
+    }
+
+    /// Update the time used by the reload policy to the time of the current frame
+    pub(crate) fn update_reload_time(&mut self, ui: &Ui) {
+        self.auto_reload.now = ui.input(|i| i.time);
+    }
+
+    /// Recreate the rows if the count or the policy is due, otherwise request a repaint for when
+    /// a time based policy will be
+    pub(crate) fn reload_if_due(&mut self, ui: &Ui) {
+        if self.auto_reload.is_due() {
+            self.recreate_rows();
+            return;
+        }
+
+        // Too long to fit a `Duration` means the reload is never due
+        let repaint_after = self
+            .auto_reload
+            .seconds_left()
+            .and_then(|seconds_left| Duration::try_from_secs_f64(seconds_left.max(0.0)).ok());
+        if let Some(repaint_after) = repaint_after {
+            ui.ctx().request_repaint_after(repaint_after);
+        }
+    }
 }
@@ -0,0 +1,175 @@
+use egui::{CursorIcon, Response, Stroke, Ui};
+use egui_extras::TableBuilder;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable, TableEvent};
+
+/// Functions related to the order of the columns
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Returns all columns in the order they are displayed, including the hidden ones.
+    ///
+    /// Can be saved and later passed to [`set_column_order`](#method.set_column_order) to restore
+    /// the order the user arranged the columns in.
+    ///
+    /// # Returns:
+    /// - `Vec<F>`: The columns in display order.
+    pub fn column_order(&self) -> Vec<F> {
+        self.all_columns.clone()
+    }
+
+    /// Changes the order the columns are displayed in.
+    ///
+    /// Columns that are not part of the table are ignored and columns of the table that are
+    /// missing from `columns` are kept at the end in their current order, so a saved order can
+    /// safely be restored after columns were added or removed.
+    ///
+    /// # Parameters:
+    /// - `columns`: The columns in the order they should be displayed.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let saved_order = table.column_order();
+    /// // later
+    /// table.set_column_order(saved_order);
+    /// ```
+    pub fn set_column_order(&mut self, columns: Vec<F>) {
+        let mut new_order = Vec::with_capacity(self.all_columns.len());
+
+        for column in columns {
+            if self.column_number.contains_key(&column) && !new_order.contains(&column) {
+                new_order.push(column);
+            }
+        }
+        for column in &self.all_columns {
+            if !new_order.contains(column) {
+                new_order.push(column.clone());
+            }
+        }
+
+        if new_order == self.all_columns {
+            return;
+        }
+
+        for (index, column) in new_order.iter().enumerate() {
+            self.column_number.insert(column.clone(), index);
+        }
+        self.all_columns = new_order;
+        self.reset_column_widths = true;
+        self.push_event(TableEvent::ColumnOrderChanged);
+    }
+
+    /// Allows the user to reorder the columns by dragging the headers.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with column reordering enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .column_reordering();
+    /// ```
+    #[must_use]
+    pub const fn column_reordering(mut self) -> Self {
+        self.column_reordering = true;
+        self
+    }
+
+    /// Sets whether the user can reorder the columns by dragging the headers.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to enable column reordering, `false` to disable it.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_column_reordering(true);
+    /// ```
+    pub const fn set_column_reordering(&mut self, status: bool) {
+        self.column_reordering = status;
+    }
+
+    /// Handle dragging a header cell and dropping it over another one. The response must sense
+    /// drags
+    pub(crate) fn handle_header_drag(&mut self, ui: &Ui, resp: &Response, column_name: &F) {
+        if resp.drag_started() {
+            self.dragged_column = Some(column_name.clone());
+        }
+
+        let Some(dragged_column) = self.dragged_column.clone() else {
+            return;
+        };
+
+        if resp.dragged() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+        }
+
+        if &dragged_column == column_name || !ui.ui_contains_pointer() {
+            return;
+        }
+
+        let Some(pointer) = ui.input(|i| i.pointer.interact_pos()) else {
+            return;
+        };
+
+        // Drop before the column if the pointer is on the left half of the header, otherwise after
+        let rect = ui.max_rect();
+        let drop_after = pointer.x > rect.center().x;
+        let line_x = if drop_after {
+            rect.right()
+        } else {
+            rect.left()
+        };
+
+        ui.painter().vline(
+            line_x,
+            rect.y_range(),
+            Stroke::new(2.0, ui.visuals().selection.stroke.color),
+        );
+
+        if ui.input(|i| i.pointer.primary_released()) {
+            self.move_column(&dragged_column, column_name, drop_after);
+        }
+    }
+
+    /// Apply the pending column width reset after the column order changed. The flag is cleared
+    /// by `show_ui` once all tables were built
+    pub(crate) fn apply_column_width_reset(&self, table: &TableBuilder) {
+        if self.reset_column_widths {
+            table.reset();
+        }
+    }
+
+    /// Move a column right before or after the target column
+    fn move_column(&mut self, column: &F, target: &F, after: bool) {
+        let mut new_order = self.all_columns.clone();
+        new_order.retain(|ongoing_column| ongoing_column != column);
+
+        let Some(target_index) = new_order
+            .iter()
+            .position(|ongoing_column| ongoing_column == target)
+        else {
+            return;
+        };
+
+        let insert_index = if after {
+            target_index + 1
+        } else {
+            target_index
+        };
+        new_order.insert(insert_index, column.clone());
+
+        self.set_column_order(new_order);
+    }
+}
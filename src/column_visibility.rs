@@ -0,0 +1,154 @@
+use egui::Ui;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// Functions related to hiding and showing columns
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Hides a column from the table. Hidden columns are not rendered, copied or selected by
+    /// select all. The last visible column cannot be hidden.
+    ///
+    /// # Considerations:
+    /// - If the columns are added in the `show_ui` builder closure, the widths will not follow the
+    ///   columns when one is hidden. Implement [`ColumnOperations::column_layout`] to let the table
+    ///   add the visible columns instead.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to hide.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.hide_column(&Column::Address);
+    /// ```
+    pub fn hide_column(&mut self, column: &F) {
+        if self.hidden_columns.contains(column) || self.visible_columns().len() == 1 {
+            return;
+        }
+
+        self.hidden_columns.insert(column.clone());
+
+        if let Some((id, focused_column)) = &self.focused_cell {
+            if focused_column == column {
+                let visible_column = self.visible_columns()[0].clone();
+                self.focused_cell = Some((*id, visible_column));
+            }
+        }
+    }
+
+    /// Shows a previously hidden column.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to show.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.show_column(&Column::Address);
+    /// ```
+    pub fn show_column(&mut self, column: &F) {
+        self.hidden_columns.remove(column);
+    }
+
+    /// Whether the column is currently visible.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to check.
+    ///
+    /// # Returns:
+    /// - `bool`: `false` if the column is hidden.
+    pub fn is_column_visible(&self, column: &F) -> bool {
+        !self.hidden_columns.contains(column)
+    }
+
+    /// Returns the columns that are currently visible, in the order they are displayed.
+    ///
+    /// # Returns:
+    /// - `Vec<F>`: The visible columns.
+    pub fn visible_columns(&self) -> Vec<F> {
+        self.all_columns
+            .iter()
+            .filter(|column| !self.hidden_columns.contains(column))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the display name of the column, from [`ColumnOperations::column_name`] or from
+    /// its position, such as `Column 2`, when the name is empty.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to name.
+    ///
+    /// # Returns:
+    /// - `String`: The name of the column.
+    pub fn column_display_name(&self, column: &F) -> String {
+        let name = column.column_name();
+        if !name.is_empty() {
+            return name;
+        }
+        let position = self
+            .all_columns
+            .iter()
+            .position(|other| other == column)
+            .unwrap_or_default();
+        format!("Column {}", position + 1)
+    }
+
+    /// Adds a context menu to the headers with a checkbox for each column, letting the user pick
+    /// which columns are visible. Uses [`column_display_name`](#method.column_display_name) for the checkbox labels.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the column visibility menu enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .column_visibility_menu();
+    /// ```
+    #[must_use]
+    pub const fn column_visibility_menu(mut self) -> Self {
+        self.column_visibility_menu = true;
+        self
+    }
+
+    /// Sets whether the headers should have a context menu for toggling column visibility.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to enable the menu, `false` to disable it.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_column_visibility_menu(true);
+    /// ```
+    pub const fn set_column_visibility_menu(&mut self, status: bool) {
+        self.column_visibility_menu = status;
+    }
+
+    /// Create the checkboxes for toggling the visibility of each column
+    pub(crate) fn column_visibility_ui(&mut self, ui: &mut Ui) {
+        for column in &self.all_columns.clone() {
+            let mut visible = self.is_column_visible(column);
+            if ui
+                .checkbox(&mut visible, self.column_display_name(column))
+                .changed()
+            {
+                if visible {
+                    self.show_column(column);
+                } else {
+                    self.hide_column(column);
+                }
+            }
+        }
+    }
+}
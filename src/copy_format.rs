@@ -0,0 +1,315 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// The format used by [`copy_selected_cells`](SelectableTable::copy_selected_cells) when
+/// copying the selected cells to the clipboard.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Pads the values with spaces so the columns line up in a text editor.
+    #[default]
+    Aligned,
+    /// Separates the values with tabs. Tabs and line breaks inside values are replaced with
+    /// spaces. Pastes into spreadsheets as separate cells.
+    Tsv,
+    /// Separates the values with commas, quoting values that contain commas, quotes or line
+    /// breaks.
+    Csv,
+    /// A Markdown table. An empty header row is added if headers are not included as Markdown
+    /// tables require one.
+    Markdown,
+    /// An HTML `<table>` with escaped values.
+    Html,
+}
+
+impl CopyFormat {
+    /// Format the cells, with optional header names, into the text that gets copied
+    pub(crate) fn format_cells(self, headers: Option<&[String]>, rows: &[Vec<String>]) -> String {
+        let mut text = String::new();
+
+        match self {
+            Self::Aligned => {
+                // Target is to ensure a fixed length after each column value of a row
+                // If for example highest len is 10 but the current row's
+                // column value is 5, we will add the column value and add 5 more space after that
+                // to ensure alignment
+                let mut widths = headers.map_or_else(Vec::new, |headers| {
+                    headers.iter().map(String::len).collect()
+                });
+                for row in rows {
+                    widths.resize(widths.len().max(row.len()), 0);
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell.len());
+                    }
+                }
+
+                for row in headers.into_iter().chain(rows.iter().map(Vec::as_slice)) {
+                    for (cell, width) in row.iter().zip(&widths) {
+                        let _ = write!(text, "{:<width$}", cell, width = width + 1);
+                    }
+                    text.push('\n');
+                }
+            }
+            Self::Tsv => {
+                for row in headers.into_iter().chain(rows.iter().map(Vec::as_slice)) {
+                    push_tsv_line(&mut text, row);
+                }
+            }
+            Self::Csv => {
+                for row in headers.into_iter().chain(rows.iter().map(Vec::as_slice)) {
+                    push_csv_line(&mut text, row);
+                }
+            }
+            Self::Markdown => {
+                let column_count = rows.first().map_or(0, Vec::len);
+                let empty_headers = vec![String::new(); column_count];
+                push_markdown_header(&mut text, headers.unwrap_or(&empty_headers));
+
+                for row in rows {
+                    push_markdown_line(&mut text, row);
+                }
+            }
+            Self::Html => {
+                text.push_str("<table>\n");
+                if let Some(headers) = headers {
+                    text.push_str("<thead>\n");
+                    push_html_line(&mut text, headers, "th");
+                    text.push_str("</thead>\n");
+                }
+                text.push_str("<tbody>\n");
+                for row in rows {
+                    push_html_line(&mut text, row, "td");
+                }
+                text.push_str("</tbody>\n</table>\n");
+            }
+        }
+        text
+    }
+}
+
+/// Add a line of tab separated values
+pub fn push_tsv_line(text: &mut String, cells: &[String]) {
+    for (index, cell) in cells.iter().enumerate() {
+        if index != 0 {
+            text.push('\t');
+        }
+        text.push_str(&escape_tsv(cell));
+    }
+    text.push('\n');
+}
+
+/// Add a line of comma separated values
+pub fn push_csv_line(text: &mut String, cells: &[String]) {
+    for (index, cell) in cells.iter().enumerate() {
+        if index != 0 {
+            text.push(',');
+        }
+        text.push_str(&escape_csv(cell));
+    }
+    text.push('\n');
+}
+
+/// Add the header line of a Markdown table along with the delimiter line
+pub fn push_markdown_header(text: &mut String, headers: &[String]) {
+    push_markdown_line(text, headers);
+
+    text.push('|');
+    for _ in headers {
+        text.push_str(" --- |");
+    }
+    text.push('\n');
+}
+
+/// Add a line of a Markdown table
+pub fn push_markdown_line(text: &mut String, cells: &[String]) {
+    text.push('|');
+    for cell in cells {
+        text.push(' ');
+        text.push_str(&escape_markdown(cell));
+        text.push_str(" |");
+    }
+    text.push('\n');
+}
+
+/// Add a `<tr>` with each cell wrapped in the given tag
+fn push_html_line(text: &mut String, cells: &[String], tag: &str) {
+    text.push_str("<tr>");
+    for cell in cells {
+        let _ = write!(text, "<{tag}>{}</{tag}>", escape_html(cell));
+    }
+    text.push_str("</tr>\n");
+}
+
+/// Replace the characters that would break the TSV structure with a space
+pub fn escape_tsv(value: &str) -> Cow<'_, str> {
+    if value.contains(['\t', '\n', '\r']) {
+        Cow::Owned(value.replace(['\t', '\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Quote the value if it contains a comma, a quote or a line break. Quotes are doubled.
+pub fn escape_csv(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Escape the pipes and replace the line breaks so the value stays in its cell
+pub fn escape_markdown(value: &str) -> Cow<'_, str> {
+    if value.contains(['|', '\n', '\r']) {
+        Cow::Owned(
+            value
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace(['\n', '\r'], "<br>"),
+        )
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Escape the characters that have a special meaning in HTML
+fn escape_html(value: &str) -> Cow<'_, str> {
+    if value.contains(['&', '<', '>', '"', '\'']) {
+        Cow::Owned(
+            value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+                .replace('\'', "&#39;"),
+        )
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Functions related to the format of the copied cells
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Sets the format used when copying the selected cells. Default: [`CopyFormat::Aligned`]
+    ///
+    /// # Parameters:
+    /// - `format`: The format of the copied text.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the new copy format.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .copy_format(CopyFormat::Tsv);
+    /// ```
+    #[must_use]
+    pub const fn copy_format(mut self, format: CopyFormat) -> Self {
+        self.copy_format = format;
+        self
+    }
+
+    /// Sets the format used when copying the selected cells.
+    ///
+    /// # Parameters:
+    /// - `format`: The format of the copied text.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_copy_format(CopyFormat::Csv);
+    /// ```
+    pub const fn set_copy_format(&mut self, format: CopyFormat) {
+        self.copy_format = format;
+    }
+
+    /// Includes the header names of the copied columns as the first line of the copied text.
+    /// Uses [`column_display_name`](#method.column_display_name) for the names.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the header names included when copying.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .copy_format(CopyFormat::Csv)
+    ///     .copy_headers();
+    /// ```
+    #[must_use]
+    pub const fn copy_headers(mut self) -> Self {
+        self.copy_headers = true;
+        self
+    }
+
+    /// Sets whether the header names of the copied columns are included when copying.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to include the header names, `false` to copy only the cells.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_copy_headers(true);
+    /// ```
+    pub const fn set_copy_headers(&mut self, status: bool) {
+        self.copy_headers = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tsv_replaces_tabs_and_line_breaks() {
+        assert_eq!(escape_tsv("plain"), "plain");
+        assert_eq!(escape_tsv("a\tb\r\nc"), "a b  c");
+    }
+
+    #[test]
+    fn csv_quotes_special_values() {
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("line\r\nbreak"), "\"line\r\nbreak\"");
+    }
+
+    #[test]
+    fn markdown_escapes_pipes_and_line_breaks() {
+        assert_eq!(escape_markdown("plain"), "plain");
+        assert_eq!(escape_markdown("a|b"), "a\\|b");
+        assert_eq!(escape_markdown("a\r\nb\nc\rd"), "a<br>b<br>c<br>d");
+    }
+
+    #[test]
+    fn html_escapes_special_characters() {
+        assert_eq!(escape_html("plain"), "plain");
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn lines_join_escaped_cells() {
+        let cells = vec![String::from("a,b"), String::from("c")];
+        let mut text = String::new();
+        push_csv_line(&mut text, &cells);
+        push_tsv_line(&mut text, &cells);
+        push_markdown_header(&mut text, &cells);
+        assert_eq!(text, "\"a,b\",c\na,b\tc\n| a,b | c |\n| --- | --- |\n");
+    }
+}
@@ -0,0 +1,451 @@
+use egui::ahash::{HashMap, HashMapExt};
+
+/// Marks a missing node
+const NIL: usize = usize::MAX;
+
+/// A node of the tree, holding one displayed row
+#[derive(Clone)]
+struct Node {
+    /// The ID of the row
+    id: i64,
+    /// The random priority keeping the tree balanced, higher is closer to the root
+    priority: u64,
+    /// The rows displayed before this one in its subtree
+    left: usize,
+    /// The rows displayed after this one in its subtree
+    right: usize,
+    /// The node above this one
+    parent: usize,
+    /// The number of rows in the subtree of this node, including itself
+    size: usize,
+}
+
+/// The IDs of the displayed rows in the order they are displayed.
+///
+/// The rows are kept in a balanced tree where every node knows how many rows are in its subtree,
+/// so a row can be found by its display index or its ID, and inserted or removed at any position,
+/// in `O(log n)`.
+#[derive(Clone)]
+pub struct DisplayedRows {
+    /// The nodes of the tree, including the freed ones
+    nodes: Vec<Node>,
+    /// Freed nodes that can be reused
+    free: Vec<usize>,
+    /// The root node of the tree
+    root: usize,
+    /// The node of each displayed row ID
+    nodes_by_id: HashMap<i64, usize>,
+    /// The state of the random number generator for the priorities
+    seed: u64,
+}
+
+impl Default for DisplayedRows {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            nodes_by_id: HashMap::new(),
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+}
+
+impl FromIterator<i64> for DisplayedRows {
+    /// Builds the tree from the IDs in display order in `O(n)`
+    fn from_iter<I: IntoIterator<Item = i64>>(ids: I) -> Self {
+        let mut displayed = Self::default();
+        let mut right_spine: Vec<usize> = Vec::new();
+
+        for id in ids {
+            let node = displayed.new_node(id);
+
+            // The nodes with a lower priority on the right edge become the left subtree
+            let mut last_popped = NIL;
+            while let Some(&top) = right_spine.last() {
+                if displayed.nodes[top].priority >= displayed.nodes[node].priority {
+                    break;
+                }
+                last_popped = top;
+                right_spine.pop();
+            }
+            displayed.nodes[node].left = last_popped;
+            if let Some(&top) = right_spine.last() {
+                displayed.nodes[top].right = node;
+            }
+            right_spine.push(node);
+        }
+        displayed.root = right_spine.first().copied().unwrap_or(NIL);
+
+        // Children come before their parents in the reversed preorder
+        let mut preorder = Vec::with_capacity(displayed.nodes.len());
+        let mut pending = vec![displayed.root];
+        while let Some(node) = pending.pop() {
+            if node == NIL {
+                continue;
+            }
+            preorder.push(node);
+            pending.push(displayed.nodes[node].left);
+            pending.push(displayed.nodes[node].right);
+        }
+        for node in preorder.into_iter().rev() {
+            displayed.update(node);
+        }
+        displayed.set_root_parent();
+        displayed
+    }
+}
+
+impl DisplayedRows {
+    /// Returns the number of displayed rows.
+    ///
+    /// # Returns:
+    /// - `usize`: The number of displayed rows.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// Whether no row is displayed.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if there are no displayed rows.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// Whether the row with the ID is displayed.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if the row is displayed.
+    #[must_use]
+    pub fn contains(&self, id: i64) -> bool {
+        self.nodes_by_id.contains_key(&id)
+    }
+
+    /// Returns the display index of the row with the ID in `O(log n)`.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `Option<usize>`: The display index, `None` if the row is not displayed.
+    #[must_use]
+    pub fn index_of(&self, id: i64) -> Option<usize> {
+        let mut node = *self.nodes_by_id.get(&id)?;
+        let mut index = self.size(self.nodes[node].left);
+
+        while self.nodes[node].parent != NIL {
+            let parent = self.nodes[node].parent;
+            if self.nodes[parent].right == node {
+                index += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+        Some(index)
+    }
+
+    /// Returns the ID of the row at the display index in `O(log n)`.
+    ///
+    /// # Parameters:
+    /// - `index`: The display index of the row.
+    ///
+    /// # Returns:
+    /// - `Option<i64>`: The ID of the row, `None` if the index is past the displayed rows.
+    #[must_use]
+    pub fn id_at(&self, mut index: usize) -> Option<i64> {
+        let mut node = self.root;
+        while node != NIL {
+            let left_size = self.size(self.nodes[node].left);
+            match index.cmp(&left_size) {
+                std::cmp::Ordering::Less => node = self.nodes[node].left,
+                std::cmp::Ordering::Equal => return Some(self.nodes[node].id),
+                std::cmp::Ordering::Greater => {
+                    index -= left_size + 1;
+                    node = self.nodes[node].right;
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the IDs of the displayed rows in the order they are displayed.
+    ///
+    /// # Returns:
+    /// - `impl Iterator<Item = i64>`: The IDs of the displayed rows.
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter_from(0)
+    }
+
+    /// The IDs of the displayed rows starting from the display index
+    pub(crate) fn iter_from(&self, mut index: usize) -> DisplayedIter<'_> {
+        let mut pending = Vec::new();
+        let mut node = self.root;
+        while node != NIL {
+            let left_size = self.size(self.nodes[node].left);
+            if index <= left_size {
+                pending.push(node);
+                if index == left_size {
+                    break;
+                }
+                node = self.nodes[node].left;
+            } else {
+                index -= left_size + 1;
+                node = self.nodes[node].right;
+            }
+        }
+
+        DisplayedIter {
+            displayed: self,
+            pending,
+        }
+    }
+
+    /// The number of leading rows for which `pred` returns `true`, assuming it returns `true` for
+    /// every row before the first one it returns `false` for
+    pub(crate) fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(i64) -> bool,
+    {
+        let mut index = 0;
+        let mut node = self.root;
+        while node != NIL {
+            if pred(self.nodes[node].id) {
+                index += self.size(self.nodes[node].left) + 1;
+                node = self.nodes[node].right;
+            } else {
+                node = self.nodes[node].left;
+            }
+        }
+        index
+    }
+
+    /// Insert the row at the display index, moving the rows from the index down by one
+    pub(crate) fn insert(&mut self, index: usize, id: i64) {
+        debug_assert!(!self.contains(id), "The row is already displayed");
+
+        let node = self.new_node(id);
+        self.nodes[node].size = 1;
+        let (before, after) = self.split(self.root, index.min(self.len()));
+        let before = self.merge(before, node);
+        self.root = self.merge(before, after);
+        self.set_root_parent();
+    }
+
+    /// Remove the row, moving the rows after it up by one. Returns the display index it had.
+    pub(crate) fn remove(&mut self, id: i64) -> Option<usize> {
+        let index = self.index_of(id)?;
+        let node = self.nodes_by_id.remove(&id)?;
+
+        let (before, rest) = self.split(self.root, index);
+        let (removed, after) = self.split(rest, 1);
+        debug_assert_eq!(removed, node);
+        self.root = self.merge(before, after);
+        self.set_root_parent();
+
+        self.free.push(node);
+        Some(index)
+    }
+
+    /// Remove every row
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.nodes_by_id.clear();
+        self.root = NIL;
+    }
+
+    /// Create a node without any links for the row
+    fn new_node(&mut self, id: i64) -> usize {
+        let node = Node {
+            id,
+            priority: self.next_priority(),
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+            size: 1,
+        };
+
+        let index = if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        };
+        self.nodes_by_id.insert(id, index);
+        index
+    }
+
+    /// A pseudo random priority, using splitmix64
+    const fn next_priority(&mut self) -> u64 {
+        self.seed = self.seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.seed;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// The number of rows in the subtree of the node
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    /// Recount the subtree of the node and point its children back to it
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+        if left != NIL {
+            self.nodes[left].parent = node;
+        }
+        if right != NIL {
+            self.nodes[right].parent = node;
+        }
+    }
+
+    /// The root has no parent
+    fn set_root_parent(&mut self) {
+        if self.root != NIL {
+            self.nodes[self.root].parent = NIL;
+        }
+    }
+
+    /// Split the subtree into the first `count` rows and the rest
+    fn split(&mut self, node: usize, count: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+
+        let left_size = self.size(self.nodes[node].left);
+        if count <= left_size {
+            let (before, after) = self.split(self.nodes[node].left, count);
+            self.nodes[node].left = after;
+            self.update(node);
+            (before, node)
+        } else {
+            let (before, after) = self.split(self.nodes[node].right, count - left_size - 1);
+            self.nodes[node].right = before;
+            self.update(node);
+            (node, after)
+        }
+    }
+
+    /// Join two subtrees, with all rows of `first` displayed before the rows of `second`
+    fn merge(&mut self, first: usize, second: usize) -> usize {
+        if first == NIL {
+            return second;
+        }
+        if second == NIL {
+            return first;
+        }
+
+        if self.nodes[first].priority > self.nodes[second].priority {
+            let right = self.merge(self.nodes[first].right, second);
+            self.nodes[first].right = right;
+            self.update(first);
+            first
+        } else {
+            let left = self.merge(first, self.nodes[second].left);
+            self.nodes[second].left = left;
+            self.update(second);
+            second
+        }
+    }
+}
+
+/// Iterates over the displayed row IDs in display order
+pub struct DisplayedIter<'a> {
+    displayed: &'a DisplayedRows,
+    /// The nodes left to visit, the next one on top
+    pending: Vec<usize>,
+}
+
+impl Iterator for DisplayedIter<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let node = self.pending.pop()?;
+        let nodes = &self.displayed.nodes;
+
+        let mut next = nodes[node].right;
+        while next != NIL {
+            self.pending.push(next);
+            next = nodes[next].left;
+        }
+        Some(nodes[node].id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(displayed: &DisplayedRows) -> Vec<i64> {
+        displayed.iter().collect()
+    }
+
+    #[test]
+    fn builds_in_order() {
+        let displayed: DisplayedRows = (0..100).collect();
+        assert_eq!(ids(&displayed), (0..100).collect::<Vec<_>>());
+        assert_eq!(displayed.len(), 100);
+        for id in 0..100 {
+            assert_eq!(displayed.index_of(id), usize::try_from(id).ok());
+            assert_eq!(
+                displayed.id_at(usize::try_from(id).unwrap_or_default()),
+                Some(id)
+            );
+        }
+        assert_eq!(displayed.id_at(100), None);
+    }
+
+    #[test]
+    fn inserts_and_removes_like_a_vec() {
+        let mut displayed = DisplayedRows::default();
+        let mut expected: Vec<i64> = Vec::new();
+
+        for id in 0..200 {
+            let index = usize::try_from(id * 7 % (id + 1)).unwrap_or_default();
+            displayed.insert(index, id);
+            expected.insert(index, id);
+        }
+        for id in (0..200).step_by(3) {
+            let index = expected.iter().position(|other| *other == id);
+            assert_eq!(displayed.remove(id), index);
+            expected.retain(|other| *other != id);
+        }
+
+        assert_eq!(ids(&displayed), expected);
+        for (index, id) in expected.iter().enumerate() {
+            assert_eq!(displayed.index_of(*id), Some(index));
+            assert_eq!(displayed.id_at(index), Some(*id));
+        }
+        assert_eq!(displayed.remove(0), None);
+    }
+
+    #[test]
+    fn iterates_from_an_index() {
+        let displayed: DisplayedRows = (10..20).collect();
+        assert_eq!(
+            displayed.iter_from(4).collect::<Vec<_>>(),
+            (14..20).collect::<Vec<_>>()
+        );
+        assert_eq!(displayed.iter_from(10).count(), 0);
+    }
+
+    #[test]
+    fn finds_the_partition_point() {
+        let displayed: DisplayedRows = (0..50).map(|id| id * 2).collect();
+        assert_eq!(displayed.partition_point(|id| id < 31), 16);
+        assert_eq!(displayed.partition_point(|_| true), 50);
+        assert_eq!(displayed.partition_point(|_| false), 0);
+    }
+}
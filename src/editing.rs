@@ -0,0 +1,299 @@
+use egui::{Key, Ui};
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable, TableEvent};
+
+/// Validates a row after a cell was modified from text, before the change is stored
+pub type EditValidator<Row, F> = Box<dyn Fn(&F, &Row) -> Result<(), String> + Send + Sync>;
+
+/// The cell that is currently being edited
+pub struct EditingCell<F> {
+    /// The ID of the row of the cell
+    pub id: i64,
+    /// The column of the cell
+    pub column: F,
+    /// The text in the editor
+    pub text: String,
+    /// Whether the editor should take the keyboard focus on the next frame
+    pub request_focus: bool,
+}
+
+/// Functions related to editing the cells inline
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Allows editing the cells inline. Double-clicking a cell or pressing Enter on the focused
+    /// cell replaces it with the editor from [`ColumnOperations::create_cell_editor`].
+    ///
+    /// In the editor, Enter commits the value, Tab commits and moves to the next cell, Shift+Tab
+    /// to the previous one and Escape cancels the edit. Clicking elsewhere commits the value. The
+    /// text is parsed with [`ColumnOperations::set_from_text`] and checked by the
+    /// [`edit_validator`](#method.edit_validator), if any.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with inline editing enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .editable();
+    /// ```
+    #[must_use]
+    pub const fn editable(mut self) -> Self {
+        self.editable = true;
+        self
+    }
+
+    /// Sets whether the cells can be edited inline.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to enable inline editing, `false` to disable it.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_editable(true);
+    /// ```
+    pub fn set_editable(&mut self, status: bool) {
+        self.editable = status;
+        if !status {
+            self.editing_cell = None;
+        }
+    }
+
+    /// Sets a callback that checks a row after one of its cells was modified from text, through
+    /// inline editing or pasting. Returning an error keeps the row unchanged.
+    ///
+    /// # Parameters:
+    /// - `validator`: A closure that receives the modified column and the row with the new value.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the validator set.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .editable()
+    ///     .edit_validator(|_column, row| {
+    ///         if row.name.is_empty() {
+    ///             Err(String::from("Name cannot be empty"))
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn edit_validator<V>(mut self, validator: V) -> Self
+    where
+        V: Fn(&F, &Row) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.edit_validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Sets or removes the callback that checks a row after one of its cells was modified from
+    /// text.
+    ///
+    /// # Parameters:
+    /// - `validator`: The new validator, or `None` to accept every parsed value.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_edit_validator(None);
+    /// ```
+    pub fn set_edit_validator(&mut self, validator: Option<EditValidator<Row, F>>) {
+        self.edit_validator = validator;
+    }
+
+    /// Starts editing a cell, with the current text of the cell in the editor. Does nothing if
+    /// inline editing is not enabled or the row is not displayed.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    /// - `column`: The column of the cell.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.start_editing(row_id, &Column::Name);
+    /// ```
+    pub fn start_editing(&mut self, id: i64, column: &F) {
+        if !self.editable || !self.is_column_visible(column) {
+            return;
+        }
+
+        if !self.formatted_rows.contains(id) {
+            return;
+        }
+        let Some(row) = self.rows.get(&id) else {
+            return;
+        };
+
+        let text = column.column_text(&row.row_data);
+        self.editing_cell = Some(EditingCell {
+            id,
+            column: column.clone(),
+            text,
+            request_focus: true,
+        });
+        self.focused_cell = Some((id, column.clone()));
+    }
+
+    /// Stops editing the current cell without saving the text in the editor.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.cancel_editing();
+    /// ```
+    pub fn cancel_editing(&mut self) {
+        self.editing_cell = None;
+    }
+
+    /// Returns the cell that is currently being edited, if any.
+    ///
+    /// # Returns:
+    /// - `Option<(i64, &F)>`: The row ID and the column of the edited cell.
+    pub fn editing_cell(&self) -> Option<(i64, &F)> {
+        self.editing_cell
+            .as_ref()
+            .map(|editing| (editing.id, &editing.column))
+    }
+
+    /// Whether the cell is currently being edited
+    pub(crate) fn is_editing(&self, id: i64, column: &F) -> bool {
+        self.editing_cell
+            .as_ref()
+            .is_some_and(|editing| editing.id == id && &editing.column == column)
+    }
+
+    /// Start editing the focused cell when Enter is pressed. An editor that is not using the
+    /// keyboard, such as one scrolled out of view, is replaced.
+    pub(crate) fn handle_edit_start(&mut self, ui: &Ui) {
+        if !self.editable || ui.ctx().wants_keyboard_input() {
+            return;
+        }
+
+        if !ui.input(|i| i.key_pressed(Key::Enter)) {
+            return;
+        }
+
+        if let Some((id, column)) = self.focused_cell.clone() {
+            self.start_editing(id, &column);
+        }
+    }
+
+    /// Show the editor of the cell that is being edited and commit, move or cancel once the
+    /// editor loses the focus
+    pub(crate) fn show_cell_editor(&mut self, ui: &mut Ui, id: i64, column: &F) {
+        let (Some(editing), Some(row)) = (self.editing_cell.as_mut(), self.rows.get(&id)) else {
+            return;
+        };
+
+        let resp = column.create_cell_editor(ui, &mut editing.text, &row.row_data);
+
+        if editing.request_focus {
+            editing.request_focus = false;
+            resp.request_focus();
+            return;
+        }
+
+        if !resp.lost_focus() {
+            return;
+        }
+
+        let (enter_pressed, tab_pressed, escape_pressed, shift_pressed) = ui.input(|i| {
+            (
+                i.key_pressed(Key::Enter),
+                i.key_pressed(Key::Tab),
+                i.key_pressed(Key::Escape),
+                i.modifiers.shift,
+            )
+        });
+
+        if escape_pressed {
+            self.editing_cell = None;
+            return;
+        }
+
+        let Some(editing) = self.editing_cell.take() else {
+            return;
+        };
+
+        match self.set_cell_text(editing.id, column, &editing.text) {
+            Ok(()) => {
+                self.commit_history();
+                self.push_event(TableEvent::CellEdited {
+                    row_id: editing.id,
+                    column: column.clone(),
+                });
+
+                if tab_pressed {
+                    self.edit_adjacent_cell(editing.id, column, shift_pressed);
+                }
+            }
+            Err(error) => {
+                self.push_event(TableEvent::EditRejected {
+                    row_id: editing.id,
+                    column: column.clone(),
+                    error,
+                });
+
+                // Let the user fix the value unless the editor was left by clicking elsewhere
+                if enter_pressed || tab_pressed {
+                    self.editing_cell = Some(EditingCell {
+                        request_focus: true,
+                        ..editing
+                    });
+                }
+            }
+        }
+    }
+
+    /// Start editing the next or the previous visible cell, wrapping to the next or the previous
+    /// displayed row
+    fn edit_adjacent_cell(&mut self, id: i64, column: &F, backwards: bool) {
+        let visible_columns = self.visible_columns();
+        let Some(row_index) = self.formatted_rows.index_of(id) else {
+            return;
+        };
+        let Some(column_index) = visible_columns.iter().position(|c| c == column) else {
+            return;
+        };
+
+        let (target_row, target_column) = if backwards {
+            if column_index > 0 {
+                (row_index, column_index - 1)
+            } else if row_index > 0 {
+                (row_index - 1, visible_columns.len() - 1)
+            } else {
+                return;
+            }
+        } else if column_index + 1 < visible_columns.len() {
+            (row_index, column_index + 1)
+        } else if row_index + 1 < self.formatted_rows.len() {
+            (row_index + 1, 0)
+        } else {
+            return;
+        };
+
+        let Some(target_id) = self.formatted_rows.id_at(target_row) else {
+            return;
+        };
+        let target_column = visible_columns[target_column].clone();
+
+        self.unselect_all();
+        self.select_single_row_cell(target_id, &target_column);
+        self.selection_anchor = Some((target_id, target_column.clone()));
+        self.scroll_to_focused = true;
+        self.start_editing(target_id, &target_column);
+    }
+}
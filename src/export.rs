@@ -0,0 +1,225 @@
+use std::fmt::Write as _;
+use std::hash::Hash;
+use std::io::{self, Write};
+
+use crate::copy_format::{push_csv_line, push_markdown_header, push_markdown_line, push_tsv_line};
+use crate::{ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable};
+
+/// Which rows are written by [`export`](SelectableTable::export).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportScope {
+    /// All rows of the table in the order they were added, including the ones hidden by the
+    /// search or the column filters.
+    All,
+    /// The displayed rows, filtered and sorted the same way as in the UI.
+    #[default]
+    Displayed,
+    /// The displayed rows with at least one selected cell. Cells that are not selected are left
+    /// empty, or omitted from the objects with [`ExportFormat::JsonLines`].
+    Selected,
+}
+
+/// The format used by [`export`](SelectableTable::export).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma separated values with a header line.
+    #[default]
+    Csv,
+    /// Tab separated values with a header line.
+    Tsv,
+    /// One JSON object per line, with the column names as keys and the column texts as values.
+    JsonLines,
+    /// A Markdown table.
+    Markdown,
+}
+
+/// Functions related to exporting the rows
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Writes the rows of the table to the writer, one line at a time, so the whole output is
+    /// never kept in memory.
+    ///
+    /// The visible columns are exported in the order they are displayed, using
+    /// [`column_display_name`](#method.column_display_name) for the header names and
+    /// [`ColumnOperations::column_text`] for the values.
+    ///
+    /// # Considerations:
+    /// - Wrap the writer in a [`BufWriter`](std::io::BufWriter) when writing to a file, as each
+    ///   line is written separately.
+    ///
+    /// # Parameters:
+    /// - `writer`: Where the rows are written to.
+    /// - `scope`: Which rows to export.
+    /// - `format`: The format of the output.
+    ///
+    /// # Errors
+    /// - Returns the first error returned by the writer. The rows written before it are kept.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let file = BufWriter::new(File::create("rows.csv")?);
+    /// table.export(file, ExportScope::Displayed, ExportFormat::Csv)?;
+    /// ```
+    pub fn export<W>(
+        &self,
+        mut writer: W,
+        scope: ExportScope,
+        format: ExportFormat,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let columns = self.visible_columns();
+        let headers: Vec<String> = columns
+            .iter()
+            .map(|column| self.column_display_name(column))
+            .collect();
+
+        let mut line = String::new();
+        match format {
+            ExportFormat::Csv => push_csv_line(&mut line, &headers),
+            ExportFormat::Tsv => push_tsv_line(&mut line, &headers),
+            ExportFormat::Markdown => push_markdown_header(&mut line, &headers),
+            ExportFormat::JsonLines => {}
+        }
+        writer.write_all(line.as_bytes())?;
+
+        let mut cells = Vec::with_capacity(columns.len());
+
+        let mut write_row = |row: &SelectableRow<Row>| -> io::Result<()> {
+            cells.clear();
+            for column in &columns {
+                let cell =
+                    if scope == ExportScope::Selected && !self.is_cell_selected(row.id, column) {
+                        None
+                    } else {
+                        Some(column.column_text(&row.row_data))
+                    };
+                cells.push(cell);
+            }
+
+            line.clear();
+            if format == ExportFormat::JsonLines {
+                push_json_line(&mut line, &headers, &cells);
+            } else {
+                let cells: Vec<String> = cells
+                    .iter_mut()
+                    .map(|cell| cell.take().unwrap_or_default())
+                    .collect();
+                match format {
+                    ExportFormat::Csv => push_csv_line(&mut line, &cells),
+                    ExportFormat::Tsv => push_tsv_line(&mut line, &cells),
+                    ExportFormat::Markdown => push_markdown_line(&mut line, &cells),
+                    ExportFormat::JsonLines => {}
+                }
+            }
+            writer.write_all(line.as_bytes())
+        };
+
+        match scope {
+            ExportScope::All => {
+                let mut ids: Vec<i64> = self.rows.keys().copied().collect();
+                ids.sort_unstable();
+                for id in ids {
+                    write_row(&self.rows[&id])?;
+                }
+            }
+            ExportScope::Displayed => {
+                for row in self.get_displayed_rows() {
+                    write_row(row)?;
+                }
+            }
+            ExportScope::Selected => {
+                for (index, _) in self.selected_rows() {
+                    if let Some(row) = self
+                        .formatted_rows
+                        .id_at(index)
+                        .and_then(|id| self.rows.get(&id))
+                    {
+                        write_row(row)?;
+                    }
+                }
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+/// Add a JSON object with the header names as keys. Cells without a value are skipped.
+fn push_json_line(line: &mut String, headers: &[String], cells: &[Option<String>]) {
+    line.push('{');
+    let mut first = true;
+    for (header, cell) in headers.iter().zip(cells) {
+        let Some(cell) = cell else {
+            continue;
+        };
+        if !first {
+            line.push(',');
+        }
+        first = false;
+        push_json_string(line, header);
+        line.push(':');
+        push_json_string(line, cell);
+    }
+    line.push_str("}\n");
+}
+
+/// Add the value as a quoted and escaped JSON string
+fn push_json_string(line: &mut String, value: &str) {
+    line.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            character if character.is_control() => {
+                let _ = write!(line, "\\u{:04x}", u32::from(character));
+            }
+            character => line.push(character),
+        }
+    }
+    line.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_string(value: &str) -> String {
+        let mut line = String::new();
+        push_json_string(&mut line, value);
+        line
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(json_string("back\\slash"), "\"back\\\\slash\"");
+        assert_eq!(json_string("a\r\nb\tc"), "\"a\\r\\nb\\tc\"");
+        assert_eq!(json_string("bell\u{7}"), "\"bell\\u0007\"");
+    }
+
+    #[test]
+    fn json_lines_skip_missing_cells() {
+        let headers = vec![String::from("id"), String::from("name")];
+        let mut line = String::new();
+        push_json_line(&mut line, &headers, &[None, Some(String::from("x"))]);
+        assert_eq!(line, "{\"name\":\"x\"}\n");
+    }
+}
@@ -0,0 +1,88 @@
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{ColumnFiltering, ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// A column filter with its value already bound, ready to be checked against a row
+pub type RowFilter<Row> = Arc<dyn Fn(&Row) -> bool + Send + Sync>;
+
+/// Functions related to filtering the rows of the table by column
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Sets the filter of a column, replacing the previous filter of the column if any. The
+    /// displayed rows are recreated immediately.
+    ///
+    /// Filters of all columns are combined, a row is only displayed if it passes every one of
+    /// them. Filtering happens before sorting and together with the active search.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to filter by.
+    /// - `filter`: The value passed to [`ColumnFiltering::filter_by`] for each row.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_column_filter(ColumnName::UserID, MyFilter::Range(100, 200));
+    /// ```
+    pub fn set_column_filter(&mut self, column: F, filter: F::Filter)
+    where
+        F: ColumnFiltering<Row> + 'static,
+    {
+        let filter_column = column.clone();
+        let row_filter: RowFilter<Row> =
+            Arc::new(move |row: &Row| filter_column.filter_by(row, &filter));
+
+        self.column_filters.insert(column, row_filter);
+        self.recreate_rows();
+    }
+
+    /// Removes the filter of a column. Recreates the displayed rows if the column had a filter.
+    ///
+    /// # Parameters:
+    /// - `column`: The column whose filter should be removed.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.remove_column_filter(&ColumnName::UserID);
+    /// ```
+    pub fn remove_column_filter(&mut self, column: &F) {
+        if self.column_filters.remove(column).is_some() {
+            self.recreate_rows();
+        }
+    }
+
+    /// Removes the filters of all columns. Recreates the displayed rows if any filter was active.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.clear_column_filters();
+    /// ```
+    pub fn clear_column_filters(&mut self) {
+        if !self.column_filters.is_empty() {
+            self.column_filters.clear();
+            self.recreate_rows();
+        }
+    }
+
+    /// Whether the column currently has an active filter.
+    ///
+    /// # Parameters:
+    /// - `column`: The column to check.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if a filter is set for the column.
+    pub fn is_column_filtered(&self, column: &F) -> bool {
+        self.column_filters.contains_key(column)
+    }
+}
@@ -0,0 +1,165 @@
+use egui::Ui;
+use egui_extras::TableBuilder;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable, TableResponse};
+
+/// The columns that are rendered by one of the `TableBuilder`s of the table
+pub struct TablePart<F> {
+    /// The columns of this part, in display order
+    pub columns: Vec<F>,
+    /// Whether the serial column is part of this part
+    pub serial_column: bool,
+    /// Separates the state of the part from the other one
+    pub id_salt: Option<&'static str>,
+    /// Whether the vertical scrollbar should be hidden as the other part shows it
+    pub hide_scroll_bar: bool,
+}
+
+/// Keeps the vertical scroll offset of the frozen and the scrolling part in sync
+#[derive(Default)]
+pub struct FrozenScroll {
+    /// The offsets of the frozen and the scrolling part after the previous frame
+    last_offsets: (f32, f32),
+    /// The offset both parts should be scrolled to on the next frame
+    pending_offset: Option<f32>,
+}
+
+/// Functions related to pinning leading columns during horizontal scrolling
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Pins the serial column and the first `count` visible columns to the left side of the
+    /// table. The remaining columns are placed in a horizontal scroll area, while the pinned ones
+    /// stay visible. Both sides scroll vertically together and drag selection works across them.
+    ///
+    /// # Considerations:
+    /// - The frozen columns are rendered by a separate `TableBuilder`, so all columns must be
+    ///   added through [`ColumnOperations::column_layout`] instead of the `show_ui` builder closure.
+    /// - The `show_ui` builder closure only applies to the scrolling columns. Show the table with
+    ///   [`show_ui_frozen`](#method.show_ui_frozen) to apply it to the frozen columns as well.
+    /// - The frozen columns should not use [`Column::remainder`](egui_extras::Column::remainder)
+    ///   as they are shrunk to their content width.
+    /// - At least one visible column is always left in the scrolling part.
+    ///
+    /// # Parameters:
+    /// - `count`: The number of leading visible columns to pin. `0` disables frozen columns.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the frozen columns set.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .serial_column()
+    ///     .frozen_columns(1);
+    /// ```
+    #[must_use]
+    pub const fn frozen_columns(mut self, count: usize) -> Self {
+        self.frozen_columns = count;
+        self
+    }
+
+    /// Sets the number of leading visible columns that are pinned to the left side of the table.
+    ///
+    /// # Parameters:
+    /// - `count`: The number of leading visible columns to pin. `0` disables frozen columns.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_frozen_columns(2);
+    /// ```
+    pub const fn set_frozen_columns(&mut self, count: usize) {
+        self.frozen_columns = count;
+    }
+
+    /// Displays the UI for the table like [`show_ui`](#method.show_ui), calling the builder
+    /// closure for both the frozen and the scrolling `TableBuilder` when
+    /// [`frozen_columns`](#method.frozen_columns) are set.
+    ///
+    /// # Parameters:
+    /// - `ui`: The UI context where the table will be rendered.
+    /// - `table_builder`: A closure that receives and modifies each `TableBuilder`.
+    ///
+    /// # Returns:
+    /// - `TableResponse<F>`: The events that happened in the table since the previous call.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let response = table.show_ui_frozen(ui, |builder| builder.striped(true));
+    /// ```
+    pub fn show_ui_frozen<Builder>(
+        &mut self,
+        ui: &mut Ui,
+        table_builder: Builder,
+    ) -> TableResponse<F>
+    where
+        Builder: Fn(TableBuilder) -> TableBuilder,
+    {
+        self.show_table(ui, &table_builder, &table_builder)
+    }
+
+    /// Split the visible columns into the frozen and the scrolling part
+    pub(crate) fn frozen_table_parts(&self) -> (TablePart<F>, TablePart<F>) {
+        let mut scrolling_columns = self.visible_columns();
+        let frozen_count = self
+            .frozen_columns
+            .min(scrolling_columns.len().saturating_sub(1));
+        let frozen_columns = scrolling_columns.drain(..frozen_count).collect();
+
+        let frozen = TablePart {
+            columns: frozen_columns,
+            serial_column: self.add_serial_column,
+            id_salt: Some("frozen_columns"),
+            hide_scroll_bar: true,
+        };
+        let scrolling = TablePart {
+            columns: scrolling_columns,
+            serial_column: false,
+            id_salt: Some("scrolling_columns"),
+            hide_scroll_bar: false,
+        };
+        (frozen, scrolling)
+    }
+
+    /// Returns the offset both parts should be scrolled to for staying in sync
+    pub(crate) const fn take_frozen_scroll(&mut self) -> Option<f32> {
+        self.frozen_scroll.pending_offset.take()
+    }
+
+    /// Compare the vertical offsets of both parts and schedule the one that was scrolled by the
+    /// user to be applied to the other part on the next frame
+    pub(crate) fn sync_frozen_scroll(&mut self, frozen_offset: f32, scrolling_offset: f32) -> bool {
+        let (last_frozen, last_scrolling) = self.frozen_scroll.last_offsets;
+        self.frozen_scroll.last_offsets = (frozen_offset, scrolling_offset);
+
+        #[allow(clippy::float_cmp)]
+        if frozen_offset == scrolling_offset {
+            return false;
+        }
+
+        #[allow(clippy::float_cmp)]
+        let target = if frozen_offset == last_frozen {
+            scrolling_offset
+        } else if scrolling_offset == last_scrolling {
+            frozen_offset
+        } else {
+            // Both moved, the scrolling part has the visible scrollbar so it takes priority
+            scrolling_offset
+        };
+
+        self.frozen_scroll.pending_offset = Some(target);
+        true
+    }
+}
@@ -0,0 +1,310 @@
+use egui::ahash::{HashSet, HashSetExt};
+use egui::{Key, Ui};
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable, TableEvent};
+
+/// The state of a row before and after a change. `None` means the row did not exist.
+pub struct RowChange<Row> {
+    /// The ID of the changed row
+    pub id: i64,
+    /// The row before the change
+    pub before: Option<Row>,
+    /// The row after the change
+    pub after: Option<Row>,
+}
+
+/// The recorded changes that can be undone and redone
+pub struct History<Row> {
+    /// Each entry holds the changes of one action, such as a paste, in the order they happened
+    undo_stack: VecDeque<Vec<RowChange<Row>>>,
+    /// The undone actions, the last one is redone first
+    redo_stack: Vec<Vec<RowChange<Row>>>,
+    /// The changes of the action that is being recorded
+    pending: Vec<RowChange<Row>>,
+    /// The maximum number of actions that can be undone
+    depth: usize,
+}
+
+impl<Row> History<Row> {
+    /// Create an empty history that keeps up to `depth` actions
+    pub const fn new(depth: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            pending: Vec::new(),
+            depth,
+        }
+    }
+}
+
+/// Functions related to undoing and redoing the row changes
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Keeps a history of the row changes so they can be undone with Ctrl+Z and redone with
+    /// Ctrl+Shift+Z, or with [`undo`](#method.undo) and [`redo`](#method.redo).
+    ///
+    /// Recorded changes are rows added, modified or removed with
+    /// [`add_modify_row`](#method.add_modify_row), removed rows, pasted values and inline edits.
+    ///
+    /// # Considerations:
+    /// - Changes made with `modify_shown_row` are not recorded.
+    /// - Every row taken with `get_mut` in the `add_modify_row` closure is recorded as changed,
+    ///   even if it was not modified.
+    /// - Every recorded change keeps a copy of the row before and after the change.
+    /// - [`clear_all_rows`](#method.clear_all_rows) clears the history.
+    /// - With row keys enabled, an undone or redone row that would take the key of another row is
+    ///   left unchanged and reported with [`TableEvent::UpdateRejected`].
+    ///
+    /// # Parameters:
+    /// - `depth`: The maximum number of actions that can be undone.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the undo history enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .undo_history(100);
+    /// ```
+    #[must_use]
+    pub fn undo_history(mut self, depth: usize) -> Self {
+        self.history = Some(History::new(depth));
+        self
+    }
+
+    /// Enables the undo history with the given depth or disables it with `None`. Any recorded
+    /// changes are discarded.
+    ///
+    /// # Parameters:
+    /// - `depth`: The maximum number of actions that can be undone, or `None` to disable the
+    ///   history.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_undo_history(Some(50));
+    /// ```
+    pub fn set_undo_history(&mut self, depth: Option<usize>) {
+        self.history = depth.map(History::new);
+    }
+
+    /// Undoes the last recorded action.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if there was an action to undo.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.undo();
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self
+            .history
+            .as_mut()
+            .and_then(|history| history.undo_stack.pop_back())
+        else {
+            return false;
+        };
+
+        self.apply_row_states(
+            changes
+                .iter()
+                .rev()
+                .map(|change| (change.id, change.before.clone())),
+        );
+
+        if let Some(history) = self.history.as_mut() {
+            history.redo_stack.push(changes);
+        }
+        self.push_event(TableEvent::Undone);
+        true
+    }
+
+    /// Redoes the last undone action.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if there was an action to redo.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.redo();
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self
+            .history
+            .as_mut()
+            .and_then(|history| history.redo_stack.pop())
+        else {
+            return false;
+        };
+
+        self.apply_row_states(
+            changes
+                .iter()
+                .map(|change| (change.id, change.after.clone())),
+        );
+
+        if let Some(history) = self.history.as_mut() {
+            history.undo_stack.push_back(changes);
+        }
+        self.push_event(TableEvent::Redone);
+        true
+    }
+
+    /// Whether there is an action that can be undone.
+    pub fn can_undo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.undo_stack.is_empty())
+    }
+
+    /// Whether there is an action that can be redone.
+    pub fn can_redo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.redo_stack.is_empty())
+    }
+
+    /// Discards all recorded changes, keeping the history enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.clear_history();
+    /// ```
+    pub fn clear_history(&mut self) {
+        if let Some(history) = self.history.as_mut() {
+            history.undo_stack.clear();
+            history.redo_stack.clear();
+            history.pending.clear();
+        }
+    }
+
+    /// Whether row changes are being recorded
+    pub(crate) const fn is_recording_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Record a row change as part of the current action
+    pub(crate) fn record_change(&mut self, id: i64, before: Option<Row>, after: Option<Row>) {
+        if let Some(history) = self.history.as_mut() {
+            history.pending.push(RowChange { id, before, after });
+        }
+    }
+
+    /// Finish the current action so it gets undone and redone as a whole
+    pub(crate) fn commit_history(&mut self) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+
+        if history.pending.is_empty() {
+            return;
+        }
+
+        let changes = std::mem::take(&mut history.pending);
+        history.undo_stack.push_back(changes);
+        history.redo_stack.clear();
+
+        while history.undo_stack.len() > history.depth {
+            history.undo_stack.pop_front();
+        }
+    }
+
+    /// Undo with Ctrl+Z and redo with Ctrl+Shift+Z unless another widget is using the keyboard
+    pub(crate) fn handle_history_keys(&mut self, ui: &Ui) {
+        if self.history.is_none() || ui.ctx().wants_keyboard_input() {
+            return;
+        }
+
+        let (undo_pressed, redo_pressed) = ui.input(|i| {
+            let z_pressed = i.modifiers.ctrl && i.key_pressed(Key::Z);
+            (
+                z_pressed && !i.modifiers.shift,
+                z_pressed && i.modifiers.shift,
+            )
+        });
+
+        if undo_pressed {
+            self.undo();
+        } else if redo_pressed {
+            self.redo();
+        }
+    }
+
+    /// Set the rows to the recorded states, in order, without recording them again. The rows are
+    /// placed together and the removed rows leave the displayed rows in a single pass.
+    fn apply_row_states<I>(&mut self, states: I)
+    where
+        I: Iterator<Item = (i64, Option<Row>)>,
+    {
+        let mut removed_ids = HashSet::new();
+        let mut row_restored = false;
+
+        self.start_placement_batch();
+        for (id, state) in states {
+            row_restored |= self.apply_row_state(id, state, &mut removed_ids);
+        }
+        self.remove_displayed_rows(&removed_ids);
+        self.end_placement_batch();
+
+        // Without the incremental sort, added back rows are only displayed once recreated
+        if row_restored && !self.incremental_sort {
+            self.recreate_rows();
+        }
+    }
+
+    /// Set the row to the recorded state without recording it again. Removed rows are added to
+    /// `removed_ids` and stay in the displayed rows until the caller removes them. A state that
+    /// would give the row the key of another row is skipped. Returns `true` if a removed row was
+    /// added back.
+    fn apply_row_state(
+        &mut self,
+        id: i64,
+        state: Option<Row>,
+        removed_ids: &mut HashSet<i64>,
+    ) -> bool {
+        let Some(row_data) = state else {
+            if let Some(row) = Arc::make_mut(&mut self.rows).remove(&id) {
+                self.unindex_row(&row.row_data, id);
+                removed_ids.insert(id);
+            }
+            return false;
+        };
+
+        // A row that is added back is checked against its own key, which it does not hold yet
+        let stored_row = self.rows.get(&id).cloned();
+        let previous_data = stored_row.as_ref().map_or(&row_data, |row| &row.row_data);
+        if let Err(error) = self.reindex_row(previous_data, &row_data, id) {
+            self.push_event(TableEvent::UpdateRejected { row_id: id, error });
+            return false;
+        }
+
+        let restored = stored_row.is_none();
+        // Dropping the clone first keeps `make_mut` from copying the row
+        drop(stored_row);
+
+        let rows = Arc::make_mut(&mut self.rows);
+        if let Some(row) = rows.get_mut(&id) {
+            Arc::make_mut(row).row_data = row_data;
+        } else {
+            rows.insert(id, Arc::new(SelectableRow { row_data, id }));
+            removed_ids.remove(&id);
+        }
+        self.place_row(id);
+        restored
+    }
+}
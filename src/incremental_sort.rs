@@ -0,0 +1,216 @@
+use std::hash::Hash;
+
+use crate::async_rebuild::{compare_rows, row_matches_query, RowQuery};
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// Functions related to keeping the displayed rows sorted as rows are added and changed
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Places added and changed rows directly at their sorted position in the displayed rows,
+    /// instead of waiting for [`recreate_rows`](#method.recreate_rows). Rows that do not match the
+    /// active search or column filters are hidden right away.
+    ///
+    /// Applies to rows added with [`add_modify_row`](#method.add_modify_row),
+    /// [`extend_rows`](#method.extend_rows) and the upsert functions, updates from the
+    /// [`update_source`](#method.update_source), pasted values, inline edits, undo and redo.
+    ///
+    /// # Considerations:
+    /// - A change that keeps the row between its neighbours costs two comparisons. Otherwise
+    ///   the row is moved to its new position with `O(log n)` comparisons with the
+    ///   [`ColumnOrdering`] of the sorted columns, and `O(log n)` to update the displayed rows.
+    ///   The selected ranges and rows after the old and the new position are shifted as well.
+    /// - Rows that are equal in every sorted column are ordered by their ID, in every sort.
+    /// - Changes made with `modify_shown_row` are not placed.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table keeping the rows sorted as they change.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .incremental_sort()
+    ///     .update_source(receiver);
+    /// ```
+    #[must_use]
+    pub const fn incremental_sort(mut self) -> Self {
+        self.incremental_sort = true;
+        self
+    }
+
+    /// Sets whether added and changed rows are placed directly at their sorted position in the
+    /// displayed rows. When enabled, the displayed rows are recreated before the next row is
+    /// placed, as they may have gone out of order while it was disabled.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to place the rows as they change, `false` to wait for the rows to be
+    ///   recreated.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_incremental_sort(true);
+    /// ```
+    pub const fn set_incremental_sort(&mut self, status: bool) {
+        if status && !self.incremental_sort {
+            self.sort_outdated = true;
+        }
+        self.incremental_sort = status;
+    }
+
+    /// Move the row to its sorted position in the displayed rows, adding it if it just started
+    /// matching the search and filters or removing it if it no longer does. Inside a placement
+    /// batch, the row is placed when the batch ends.
+    pub(crate) fn place_row(&mut self, id: i64) {
+        self.note_placed_row(id);
+
+        if !self.incremental_sort {
+            return;
+        }
+
+        if let Some(batch) = self.placement_batch.as_mut() {
+            batch.push(id);
+            return;
+        }
+
+        let query = self.row_query();
+        self.place_rows(&[id], &query);
+    }
+
+    /// Collect the rows placed until [`end_placement_batch`](#method.end_placement_batch) so they
+    /// are placed together, with the query built once
+    pub(crate) fn start_placement_batch(&mut self) {
+        if self.incremental_sort && self.placement_batch.is_none() {
+            self.placement_batch = Some(Vec::new());
+        }
+    }
+
+    /// Place the rows collected since
+    /// [`start_placement_batch`](#method.start_placement_batch)
+    pub(crate) fn end_placement_batch(&mut self) {
+        let Some(mut ids) = self.placement_batch.take() else {
+            return;
+        };
+        if ids.is_empty() {
+            return;
+        }
+        ids.sort_unstable();
+        ids.dedup();
+
+        let query = self.row_query();
+        self.place_rows(&ids, &query);
+    }
+
+    /// Whether the row is displayed if it exists and matches the query
+    fn is_shown_by_query(&self, id: i64, query: &RowQuery<Row, F>) -> bool {
+        self.rows
+            .get(&id)
+            .is_some_and(|row| row_matches_query::<Row, F, Conf>(&row.row_data, query))
+    }
+
+    /// Whether the displayed row at the index is sorted after the row before it and before the
+    /// row after it. Rows that are no longer stored do not affect the order.
+    fn is_in_order(&self, index: usize, query: &RowQuery<Row, F>) -> bool {
+        let Some(row) = self
+            .formatted_rows
+            .id_at(index)
+            .and_then(|id| self.rows.get(&id))
+        else {
+            return true;
+        };
+
+        let after_previous = index
+            .checked_sub(1)
+            .and_then(|previous| self.formatted_rows.id_at(previous))
+            .and_then(|previous| self.rows.get(&previous))
+            .is_none_or(|previous| compare_rows(previous, row, &query.sort_stack).is_lt());
+        let before_next = self
+            .formatted_rows
+            .id_at(index + 1)
+            .and_then(|next| self.rows.get(&next))
+            .is_none_or(|next| compare_rows(row, next, &query.sort_stack).is_lt());
+        after_previous && before_next
+    }
+
+    /// Whether the row is already where it should be, displayed in order or hidden
+    fn keeps_position(&self, id: i64, query: &RowQuery<Row, F>) -> bool {
+        let displayed = self.is_shown_by_query(id, query);
+        self.formatted_rows
+            .index_of(id)
+            .map_or(!displayed, |index| {
+                displayed && self.is_in_order(index, query)
+            })
+    }
+
+    /// Take the rows out of the displayed rows, then insert the ones that are still displayed at
+    /// their sorted position, so that every position is searched among rows that are in order
+    pub(crate) fn place_rows(&mut self, ids: &[i64], query: &RowQuery<Row, F>) {
+        // The displayed rows may be out of order, so they are sorted again instead
+        if self.sort_outdated {
+            if !self.is_rebuilding() {
+                self.recreate_rows();
+            }
+            return;
+        }
+
+        // Changes that do not affect the order keep the rows where they are
+        if ids.iter().all(|id| self.keeps_position(*id, query)) {
+            return;
+        }
+
+        let mut taken_rows = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let mut selected_columns = None;
+            if let Some(index) = self.formatted_rows.remove(id) {
+                selected_columns = self.selection.columns_at(index).cloned();
+                self.selection.remove_index(index);
+                if let Some(drag_base) = self.drag_base.as_mut() {
+                    drag_base.remove_index(index);
+                }
+            }
+            taken_rows.push((id, selected_columns));
+        }
+
+        let mut hidden_rows = false;
+        for (id, selected_columns) in taken_rows {
+            let new_index = self
+                .rows
+                .get(&id)
+                .filter(|_| self.is_shown_by_query(id, query))
+                .map(|row| {
+                    self.formatted_rows.partition_point(|other| {
+                        self.rows.get(&other).is_some_and(|other| {
+                            compare_rows(other, row, &query.sort_stack).is_lt()
+                        })
+                    })
+                });
+
+            if let Some(index) = new_index {
+                self.formatted_rows.insert(index, id);
+                self.selection.insert_index(index, selected_columns);
+                if let Some(drag_base) = self.drag_base.as_mut() {
+                    drag_base.insert_index(index, None);
+                }
+            } else {
+                hidden_rows = true;
+                if selected_columns.is_some_and(|columns| !columns.is_empty()) {
+                    self.mark_selection_changed();
+                }
+            }
+        }
+
+        if hidden_rows {
+            self.clear_stale_cursor_state();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use egui::{Key, Ui};
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// Functions related to moving the focused cell with the keyboard
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Move the focused cell based on the navigation keys pressed this frame. Without Shift the
+    /// focused cell becomes the only selected cell, with Shift the selection is extended from the
+    /// selection anchor to the focused cell.
+    pub(crate) fn handle_keyboard_navigation(&mut self, ui: &Ui) {
+        // Some other widget like a text edit is using the keyboard
+        if ui.ctx().wants_keyboard_input() {
+            return;
+        }
+
+        let Some((focused_id, focused_column)) = self.focused_cell.clone() else {
+            return;
+        };
+
+        let Some(row_index) = self.formatted_rows.index_of(focused_id) else {
+            return;
+        };
+
+        // Hidden columns are skipped when moving the focus
+        let visible_columns = self.visible_columns();
+        let column_index = visible_columns
+            .iter()
+            .position(|column| column == &focused_column)
+            .unwrap_or_default();
+        let last_row = self.formatted_rows.len() - 1;
+        let last_column = visible_columns.len() - 1;
+        let page = self.rows_per_page.max(1);
+
+        let (is_ctrl_pressed, is_shift_pressed) =
+            ui.input(|i| (i.modifiers.ctrl, i.modifiers.shift));
+
+        let target = ui.input(|i| {
+            if i.key_pressed(Key::ArrowUp) {
+                Some((row_index.saturating_sub(1), column_index))
+            } else if i.key_pressed(Key::ArrowDown) {
+                Some(((row_index + 1).min(last_row), column_index))
+            } else if i.key_pressed(Key::ArrowLeft) {
+                Some((row_index, column_index.saturating_sub(1)))
+            } else if i.key_pressed(Key::ArrowRight) {
+                Some((row_index, (column_index + 1).min(last_column)))
+            } else if i.key_pressed(Key::PageUp) {
+                Some((row_index.saturating_sub(page), column_index))
+            } else if i.key_pressed(Key::PageDown) {
+                Some(((row_index + page).min(last_row), column_index))
+            } else if i.key_pressed(Key::Home) {
+                if is_ctrl_pressed {
+                    Some((0, 0))
+                } else {
+                    Some((row_index, 0))
+                }
+            } else if i.key_pressed(Key::End) {
+                if is_ctrl_pressed {
+                    Some((last_row, last_column))
+                } else {
+                    Some((row_index, last_column))
+                }
+            } else {
+                None
+            }
+        });
+
+        let Some((target_row, target_column)) = target else {
+            return;
+        };
+
+        let Some(target_id) = self.formatted_rows.id_at(target_row) else {
+            return;
+        };
+        let target_cell = (target_id, visible_columns[target_column].clone());
+
+        if is_shift_pressed {
+            let anchor = self
+                .selection_anchor
+                .clone()
+                .unwrap_or((focused_id, focused_column));
+            self.select_cell_range(&anchor, &target_cell);
+            self.selection_anchor = Some(anchor);
+        } else {
+            self.unselect_all();
+            self.select_single_row_cell(target_cell.0, &target_cell.1);
+            self.selection_anchor = Some(target_cell.clone());
+        }
+
+        self.focused_cell = Some(target_cell);
+        self.scroll_to_focused = true;
+    }
+
+    /// Returns the display index of the focused cell's row if the table should scroll to it
+    pub(crate) fn take_focus_scroll(&mut self) -> Option<usize> {
+        if !self.scroll_to_focused {
+            return None;
+        }
+        self.scroll_to_focused = false;
+
+        let (id, _) = self.focused_cell.as_ref()?;
+        self.formatted_rows.index_of(*id)
+    }
+
+    /// Returns the cell that currently has the keyboard cursor, if any.
+    ///
+    /// A cell gets focused when it is clicked and the focus can then be moved with the arrow keys,
+    /// Home/End, PageUp/PageDown and Ctrl+Home/End. Holding Shift while moving extends the selection.
+    ///
+    /// # Returns:
+    /// - `Option<&(i64, F)>`: The row ID and the column of the focused cell.
+    pub const fn focused_cell(&self) -> Option<&(i64, F)> {
+        self.focused_cell.as_ref()
+    }
+}
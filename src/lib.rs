@@ -1,18 +1,56 @@
+mod async_rebuild;
 mod auto_reload;
 mod auto_scroll;
+mod column_order;
+mod column_visibility;
+mod copy_format;
+mod displayed_rows;
+mod editing;
+mod export;
+mod filter;
+mod frozen_columns;
+mod history;
+mod incremental_sort;
+mod keyboard_navigation;
+mod paste;
+mod response;
+mod row_key;
 mod row_selection;
+mod rows_mut;
+mod search;
+mod selection;
+mod update_source;
 
+use async_rebuild::{filter_rows, sort_filtered_rows, AsyncRebuild};
 use auto_reload::AutoReload;
+pub use auto_reload::ReloadPolicy;
 pub use auto_scroll::AutoScroll;
+pub use copy_format::CopyFormat;
+pub use displayed_rows::DisplayedRows;
+pub use editing::EditValidator;
+use editing::EditingCell;
 use egui::ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
-use egui::{Event, Key, Label, Response, ScrollArea, Sense, Ui};
+use egui::scroll_area::{ScrollAreaOutput, ScrollBarVisibility};
+use egui::{Event, Key, Label, Response, ScrollArea, Sense, TextEdit, Ui};
 use egui_extras::{Column, TableBuilder, TableRow};
-use rayon::prelude::*;
+pub use export::{ExportFormat, ExportScope};
+use filter::RowFilter;
+use frozen_columns::{FrozenScroll, TablePart};
+use history::History;
+pub use response::{TableEvent, TableResponse};
+use row_key::KeyIndex;
+pub use row_key::RowKey;
+use rows_mut::RowsChanges;
+pub use rows_mut::RowsMut;
+pub use selection::Selection;
 use std::cmp::Ordering;
 use std::hash::Hash;
+use std::sync::Arc;
+use update_source::LiveUpdates;
+pub use update_source::{TableUpdate, UpdateSource};
 
 /// Enum representing the possible sort orders for table columns.
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SortOrder {
     /// Sorts in ascending order (e.g., A to Z or 1 to 10).
     #[default]
@@ -21,6 +59,16 @@ pub enum SortOrder {
     Descending,
 }
 
+impl SortOrder {
+    /// The opposite sort order
+    const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
 /// Trait for defining how to order rows based on a specific column.
 ///
 /// This trait should be implemented by users to specify how rows should be
@@ -44,6 +92,9 @@ pub enum SortOrder {
 ///     }
 /// }
 /// ```
+///
+/// When sorting by multiple columns, `order_by` of each column in the sort stack is called in turn
+/// until one of them returns an ordering other than `Ordering::Equal`.
 pub trait ColumnOrdering<Row>
 where
     Row: Clone + Send + Sync,
@@ -65,6 +116,50 @@ where
     fn order_by(&self, row_1: &Row, row_2: &Row) -> Ordering;
 }
 
+/// Trait for defining how rows are filtered based on a specific column.
+///
+/// Each column decides whether a row passes a filter value of your choice. The filter type can
+/// hold anything that makes sense for your columns, such as numeric ranges, a set of allowed
+/// values or date windows. Filters are set per column with
+/// [`SelectableTable::set_column_filter`] and a row is only displayed if it passes all of them.
+///
+/// # Example
+/// ```rust,ignore
+/// enum MyFilter {
+///     Range(u64, u64),
+///     OneOf(Vec<Status>),
+/// }
+///
+/// impl ColumnFiltering<MyRow> for ColumnName {
+///     type Filter = MyFilter;
+///
+///     fn filter_by(&self, row: &MyRow, filter: &MyFilter) -> bool {
+///         match (self, filter) {
+///             (ColumnName::UserID, MyFilter::Range(min, max)) => (*min..=*max).contains(&row.user_id),
+///             (ColumnName::Status, MyFilter::OneOf(allowed)) => allowed.contains(&row.status),
+///             _ => true,
+///         }
+///     }
+/// }
+/// ```
+pub trait ColumnFiltering<Row>
+where
+    Row: Clone + Send + Sync,
+{
+    /// The value that the rows are checked against for this column.
+    type Filter: Send + Sync + 'static;
+
+    /// Check whether the row passes the filter for this column.
+    ///
+    /// # Arguments
+    /// * `row` - The row to check.
+    /// * `filter` - The active filter value of this column.
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the row should be displayed.
+    fn filter_by(&self, row: &Row, filter: &Self::Filter) -> bool;
+}
+
 /// Trait for defining column-specific operations in a table UI.
 ///
 /// This trait allows users to define how each column should behave within a table.
@@ -96,13 +191,17 @@ where
     /// Create the header UI for this column.
     ///
     /// This function is responsible for creating the visual representation of the column header.
-    /// The `sort_order` argument indicates whether the column is currently used for sorting and, if so, in which
-    /// direction (ascending or descending). You can customize the header appearance based on
-    /// this information, for example by adding icons or text. Return `None` for no header.
+    /// The `sort_order` argument indicates whether the column is currently used for sorting and, if so, its
+    /// position in the sort stack (0 being the primary sort key) and the direction (ascending or descending).
+    /// You can customize the header appearance based on this information, for example by adding icons
+    /// or text such as "1▲ 2▼". Return `None` for no header.
+    ///
+    /// Clicking a header sorts by that column only, Shift + click adds the column to the sort
+    /// stack as the next key. Clicking a column that is already sorted flips its order.
     ///
     /// # Arguments
     /// * `ui` - A mutable reference to the UI context.
-    /// * `sort_order` - An optional `SortOrder` representing the current sort state of the column.
+    /// * `sort_order` - The position of the column in the sort stack and its `SortOrder`, if the column is sorted.
     /// * `table` - A mutable reference to the `SelectableTable`, allowing you to interact with the table state.
     ///
     /// # Returns
@@ -110,7 +209,7 @@ where
     fn create_header(
         &self,
         ui: &mut Ui,
-        sort_order: Option<SortOrder>,
+        sort_order: Option<(usize, SortOrder)>,
         table: &mut SelectableTable<Row, F, Conf>,
     ) -> Option<Response>;
 
@@ -120,10 +219,16 @@ where
     /// It should handle user interactions like clicking or selection as necessary. Mutable table
     /// access is provided for modifyiing other rows as necessary.
     ///
+    /// The row is shared with the table while it is drawn, so modifying the same row through the
+    /// table copies it. Modify it in response to an interaction, such as a click, instead of on
+    /// every frame.
+    ///
     /// # Arguments
     /// * `ui` - A mutable reference to the UI context.
     /// * `row` - A reference to the current `SelectableRow` for this table.
     /// * `column_selected` - A boolean indicating whether this column is selected.
+    /// * `column_focused` - A boolean indicating whether this cell has the keyboard cursor. Can be
+    ///   used to draw a focus ring around the cell.
     /// * `table` - A mutable reference to the `SelectableTable` for modifying table data
     ///
     /// # Returns
@@ -131,8 +236,9 @@ where
     fn create_table_row(
         &self,
         ui: &mut Ui,
-        row: &SelectableRow<Row, F>,
+        row: &SelectableRow<Row>,
         column_selected: bool,
+        column_focused: bool,
         table: &mut SelectableTable<Row, F, Conf>,
     ) -> Response;
 
@@ -148,32 +254,97 @@ where
     /// # Returns
     /// * `String` - The text representation of this column for the row.
     fn column_text(&self, row: &Row) -> String;
+
+    /// The display name of this column.
+    ///
+    /// Used wherever the table needs to name a column on its own, such as the checkboxes of the
+    /// column visibility menu or the header names of copied and exported cells. Returns an empty
+    /// string by default, in which case the table names the column by its position, such as
+    /// `Column 2`.
+    ///
+    /// # Returns
+    /// * `String` - The name of the column.
+    fn column_name(&self) -> String {
+        String::new()
+    }
+
+    /// The layout of this column, such as its width and whether it can be resized.
+    ///
+    /// When this returns `Some`, the table adds the visible columns to the `TableBuilder` itself in
+    /// the order they are displayed, so a column keeps its own layout when another column is hidden.
+    /// The columns must then not be added in the `show_ui` builder closure. Returns `None` by
+    /// default, in which case the columns are expected to be added in the builder closure.
+    ///
+    /// # Returns
+    /// * `Option<Column>` - The layout of the column.
+    fn column_layout(&self) -> Option<Column> {
+        None
+    }
+
+    /// Parse the text and store the value of this column in the row.
+    ///
+    /// Used when pasting clipboard text into the table. Returns an error by default, which means
+    /// the column cannot be modified from text.
+    ///
+    /// # Arguments
+    /// * `row` - The row to modify.
+    /// * `text` - The text to parse.
+    ///
+    /// # Errors
+    /// * Returns a message describing why the text could not be used as a value. The message is
+    ///   passed back to the app through the table events.
+    fn set_from_text(&self, _row: &mut Row, _text: &str) -> Result<(), String> {
+        let name = self.column_name();
+        if name.is_empty() {
+            Err("The column cannot be modified".to_string())
+        } else {
+            Err(format!("{name} cannot be modified"))
+        }
+    }
+
+    /// Create the editor UI of this column for inline editing.
+    ///
+    /// The text starts as the [`column_text`](#tymethod.column_text) of the cell and is passed to
+    /// [`set_from_text`](#method.set_from_text) once the edit is committed. Uses a single line
+    /// `TextEdit` filling the cell by default. The returned response must be the one of the widget
+    /// that takes the keyboard focus.
+    ///
+    /// # Arguments
+    /// * `ui` - A mutable reference to the UI context of the cell.
+    /// * `text` - The text being edited.
+    /// * `row` - The row of the cell.
+    ///
+    /// # Returns
+    /// * `Response` - The response of the editor widget.
+    fn create_cell_editor(&self, ui: &mut Ui, text: &mut String, _row: &Row) -> Response {
+        ui.add_sized(ui.available_size(), TextEdit::singleline(text))
+    }
 }
 
 /// Represents a row in a table with selectable columns.
 ///
-/// This struct is used to store the data of a row along with its unique identifier (`id`)
-/// and the set of selected columns for this row.
+/// This struct is used to store the data of a row along with its unique identifier (`id`). The
+/// selected cells are kept by the table, see
+/// [`is_cell_selected`](SelectableTable::is_cell_selected).
 ///
 /// # Type Parameters:
 /// * `Row` - The type representing the data stored in each row.
-/// * `F` - The type used to identify each column, typically an enum or a type with unique values.
 ///
 /// # Fields:
 /// * `row_data` - The actual data stored in the row.
 /// * `id` - A unique identifier for the row.
-/// * `selected_columns` - A set of columns that are selected in this row.
 #[derive(Clone)]
-pub struct SelectableRow<Row, F>
+pub struct SelectableRow<Row>
 where
     Row: Clone + Send + Sync,
-    F: Eq + Hash + Clone + Ord + Send + Sync + Default,
 {
     pub row_data: Row,
     pub id: i64,
-    pub selected_columns: HashSet<F>,
 }
 
+/// Calculates the height of a body row based on its data
+type RowHeightFn<Row> = Box<dyn Fn(&Row) -> f32 + Send + Sync>;
+
 /// A table structure that hold data for performing selection on drag, sorting, and displaying rows and more.
 ///
 /// # Type Parameters
@@ -199,28 +370,39 @@ where
     all_columns: Vec<F>,
     /// Maps each column to its index in the table for quick lookup.
     column_number: HashMap<F, usize>,
-    /// Stores all rows in the table, keyed by their unique ID.
-    rows: HashMap<i64, SelectableRow<Row, F>>,
-    /// The current set of formatted rows for display.
-    formatted_rows: Vec<SelectableRow<Row, F>>,
-    /// The column currently being used to sort the table.
-    sorted_by: F,
-    /// The current sort order (ascending or descending).
-    sort_order: SortOrder,
+    /// Stores all rows in the table, keyed by their unique ID. The rows are shared with the cells
+    /// being drawn and the map with the background rebuilds, so both are copied on write.
+    rows: Arc<HashMap<i64, Arc<SelectableRow<Row>>>>,
+    /// The IDs of the displayed rows in the order they are displayed, with their display index.
+    /// The rows themselves are only stored in `rows`.
+    formatted_rows: DisplayedRows,
+    /// The columns used to sort the table along with their sort order. The first one is the
+    /// primary sort key, the rest are only used to break ties.
+    sort_stack: Vec<(F, SortOrder)>,
     /// Tracks where a drag operation started in the table, if any.
     drag_started_on: Option<(i64, F)>,
-    /// The columns that have at least 1 row with the column as selected
-    active_columns: HashSet<F>,
-    /// The rows that have at least 1 column as selected
-    active_rows: HashSet<i64>,
+    /// The selected cells of the displayed rows
+    selection: Selection<F>,
+    /// The selection when the ongoing drag started, the dragged rectangle is added on top of it
+    drag_base: Option<Selection<F>>,
     /// The last row where the pointer was
     last_active_row: Option<i64>,
     /// The last column where the pointer was
     last_active_column: Option<F>,
     /// Whether the pointer moved from the dragged point at least once
     beyond_drag_point: bool,
-    /// Map of the row IDs to the indices of `formatted_rows`
-    indexed_ids: HashMap<i64, usize>,
+    /// The cell that has the keyboard cursor
+    focused_cell: Option<(i64, F)>,
+    /// The cell where a range selection with Shift starts from
+    selection_anchor: Option<(i64, F)>,
+    /// Whether to scroll the table to the focused cell on the next frame
+    scroll_to_focused: bool,
+    /// The number of rows that fit in the table body, used for moving the focused cell by a page
+    rows_per_page: usize,
+    /// Events that happened since the last `show_ui` call
+    events: Vec<TableEvent<F>>,
+    /// Whether the selection was modified since the last `show_ui` call
+    selection_changed: bool,
     /// The last ID that was used for a new row in the table.
     last_id_used: i64,
     /// Handles auto scroll operation when dragging
@@ -231,6 +413,60 @@ where
     select_full_row: bool,
     /// Whether to add a horizontal scrollbar
     horizontal_scroll: bool,
+    /// The format of the text created when copying the selected cells
+    copy_format: CopyFormat,
+    /// Whether to include the header names when copying the selected cells
+    copy_headers: bool,
+    /// Whether clipboard text can be pasted into the cells
+    allow_paste: bool,
+    /// Whether the cells can be edited inline
+    editable: bool,
+    /// The cell that is currently being edited
+    editing_cell: Option<EditingCell<F>>,
+    /// Checks the rows modified from text before the change is stored
+    edit_validator: Option<EditValidator<Row, F>>,
+    /// The recorded row changes for undo and redo, if enabled
+    history: Option<History<Row>>,
+    /// Maps the `RowKey` of the rows to their IDs, if enabled
+    key_index: Option<Box<dyn KeyIndex<Row>>>,
+    /// The source of row updates drained on every frame
+    live_updates: LiveUpdates<Row>,
+    /// Filters and sorts the rows on a background thread, if enabled
+    async_rebuild: Option<AsyncRebuild<Row, F>>,
+    /// Whether to show a spinner in the header while the rows are rebuilt in the background
+    rebuild_spinner: bool,
+    /// Whether added and changed rows are placed at their sorted position right away
+    incremental_sort: bool,
+    /// Whether the displayed rows must be recreated before rows can be placed in them
+    sort_outdated: bool,
+    /// The rows to place together once the running batch of changes ends
+    placement_batch: Option<Vec<i64>>,
+    /// The number of leading visible columns pinned to the left side of the table
+    frozen_columns: usize,
+    /// Keeps the vertical scroll of the frozen columns in sync with the other columns
+    frozen_scroll: FrozenScroll,
+    /// The columns that are not displayed
+    hidden_columns: HashSet<F>,
+    /// Whether to add a context menu to the headers for toggling column visibility
+    column_visibility_menu: bool,
+    /// Whether the columns can be reordered by dragging the headers
+    column_reordering: bool,
+    /// The column whose header is currently being dragged
+    dragged_column: Option<F>,
+    /// Whether the column widths should be reset on the next frame as the column order changed
+    reset_column_widths: bool,
+    /// The height of the header row
+    header_height: f32,
+    /// The height of each body row, unless `row_height_fn` is set
+    row_height: f32,
+    /// Calculates the height of each body row based on its data
+    row_height_fn: Option<RowHeightFn<Row>>,
+    /// The search query used to filter the displayed rows
+    search_query: Option<String>,
+    /// The columns that are checked when searching. All columns are checked if `None`
+    search_columns: Option<HashSet<F>>,
+    /// The active filter of each column. A row must pass all of them to be displayed
+    column_filters: HashMap<F, RowFilter<Row>>,
     /// Additional Parameters passed by you, available when creating new rows or header. Can
     /// contain anything implementing the `Default` trait
     pub config: Conf,
@@ -277,21 +513,52 @@ where
             all_columns,
             column_number,
             last_id_used: 0,
-            rows: HashMap::new(),
-            formatted_rows: Vec::new(),
-            sorted_by: F::default(),
-            sort_order: SortOrder::default(),
+            rows: Arc::default(),
+            formatted_rows: DisplayedRows::default(),
+            sort_stack: vec![(F::default(), SortOrder::default())],
             drag_started_on: None,
-            active_columns: HashSet::new(),
-            active_rows: HashSet::new(),
+            selection: Selection::default(),
+            drag_base: None,
             last_active_row: None,
             last_active_column: None,
             beyond_drag_point: false,
-            indexed_ids: HashMap::new(),
+            focused_cell: None,
+            selection_anchor: None,
+            scroll_to_focused: false,
+            rows_per_page: 0,
+            events: Vec::new(),
+            selection_changed: false,
             auto_scroll: AutoScroll::default(),
             auto_reload: AutoReload::default(),
             select_full_row: false,
             horizontal_scroll: false,
+            copy_format: CopyFormat::default(),
+            copy_headers: false,
+            allow_paste: false,
+            editable: false,
+            editing_cell: None,
+            edit_validator: None,
+            history: None,
+            key_index: None,
+            live_updates: LiveUpdates::default(),
+            async_rebuild: None,
+            rebuild_spinner: false,
+            incremental_sort: false,
+            sort_outdated: false,
+            placement_batch: None,
+            frozen_columns: 0,
+            frozen_scroll: FrozenScroll::default(),
+            hidden_columns: HashSet::new(),
+            column_visibility_menu: false,
+            column_reordering: false,
+            dragged_column: None,
+            reset_column_widths: false,
+            header_height: 20.0,
+            row_height: 25.0,
+            row_height_fn: None,
+            search_query: None,
+            search_columns: None,
+            column_filters: HashMap::new(),
             config: Conf::default(),
             add_serial_column: false,
         }
@@ -329,17 +596,23 @@ where
         self
     }
 
-    /// Clears all rows from the table, including the displayed ones
+    /// Clears all rows from the table, including the displayed ones. The undo history is cleared
+    /// as well.
     ///
     /// # Example:
     /// ```rust,ignore
     /// table.clear_all_rows();
     /// ```
     pub fn clear_all_rows(&mut self) {
-        self.rows.clear();
+        self.rows = Arc::default();
         self.formatted_rows.clear();
-        self.active_rows.clear();
-        self.active_columns.clear();
+        self.selection.clear();
+        self.drag_base = None;
+        self.focused_cell = None;
+        self.selection_anchor = None;
+        self.editing_cell = None;
+        self.clear_history();
+        self.clear_key_index();
         self.last_id_used = 0;
     }
 
@@ -348,138 +621,298 @@ where
     /// # Parameters:
     /// - `ui`: The UI context where the table will be rendered.
     /// - `table_builder`: A closure that receives and modifies the `TableBuilder`.
+    ///   With [`frozen_columns`](#method.frozen_columns) it is only called for the scrolling
+    ///   `TableBuilder`. Use [`show_ui_frozen`](#method.show_ui_frozen) to call it for both.
+    ///
+    /// # Returns:
+    /// - `TableResponse<F>`: The events that happened in the table since the previous call, such as
+    ///   selection or sort changes and clicked cells.
     ///
     /// # Example:
     /// ```rust,ignore
-    /// table.show_ui(ui, |builder| builder.column(column1));
+    /// let response = table.show_ui(ui, |builder| builder.column(column1));
+    /// if response.selection_changed() {
+    ///     // react to the new selection
+    /// }
     /// ```
-    pub fn show_ui<Fn>(&mut self, ui: &mut Ui, table_builder: Fn)
+    pub fn show_ui<Builder>(&mut self, ui: &mut Ui, table_builder: Builder) -> TableResponse<F>
     where
-        Fn: FnOnce(TableBuilder) -> TableBuilder,
+        Builder: FnOnce(TableBuilder) -> TableBuilder,
     {
-        let is_ctrl_pressed = ui.ctx().input(|i| i.modifiers.ctrl);
-        let key_a_pressed = ui.ctx().input(|i| i.key_pressed(Key::A));
-        let copy_initiated = ui.ctx().input(|i| i.events.contains(&Event::Copy));
-        let ctx = ui.ctx().clone();
+        self.show_table(ui, |table| table, table_builder)
+    }
 
-        if copy_initiated {
-            self.copy_selected_cells(ui);
-        }
-        if is_ctrl_pressed && key_a_pressed {
-            self.select_all();
+    /// Show the table, with `frozen_builder` used for the `TableBuilder` of the frozen columns
+    /// and `table_builder` for the other one
+    pub(crate) fn show_table<FrozenBuilder, Builder>(
+        &mut self,
+        ui: &mut Ui,
+        frozen_builder: FrozenBuilder,
+        table_builder: Builder,
+    ) -> TableResponse<F>
+    where
+        FrozenBuilder: FnOnce(TableBuilder) -> TableBuilder,
+        Builder: FnOnce(TableBuilder) -> TableBuilder,
+    {
+        // Select all and copy belong to the text edit while one, such as the cell editor, is using
+        // the keyboard
+        if !ui.ctx().wants_keyboard_input() {
+            let is_ctrl_pressed = ui.ctx().input(|i| i.modifiers.ctrl);
+            let key_a_pressed = ui.ctx().input(|i| i.key_pressed(Key::A));
+            let copy_initiated = ui.ctx().input(|i| i.events.contains(&Event::Copy));
+
+            if copy_initiated {
+                self.copy_selected_cells(ui);
+            }
+            if is_ctrl_pressed && key_a_pressed {
+                self.select_all();
+            }
         }
+        self.update_reload_time(ui);
+        self.drain_update_source(ui);
+        self.reload_if_due(ui);
+        self.poll_async_rebuild(ui);
+        self.handle_history_keys(ui);
+        self.handle_paste(ui);
+        self.handle_edit_start(ui);
+        self.handle_keyboard_navigation(ui);
 
+        let ctx = ui.ctx().clone();
         let pointer = ui.input(|i| i.pointer.hover_pos());
         let max_rect = ui.max_rect();
 
-        if self.horizontal_scroll {
-            ScrollArea::horizontal().show(ui, |ui| {
-                let mut table = TableBuilder::new(ui);
+        let mut scroll_offset = None;
+        if self.drag_started_on.is_some() {
+            if let Some(offset) = self.auto_scroll.start_scroll(max_rect, pointer) {
+                scroll_offset = Some(offset);
+                ctx.request_repaint();
+            }
+        }
+        let frozen_offset = self.take_frozen_scroll();
+        let scroll_offset = scroll_offset.or(frozen_offset);
+        let scroll_to_row = self.take_focus_scroll();
 
-                if self.add_serial_column {
-                    table = table.column(Column::initial(25.0).clip(true));
-                }
+        let output = if self.frozen_columns > 0 {
+            let (frozen, scrolling) = self.frozen_table_parts();
 
-                table = table_builder(table);
+            ui.horizontal_top(|ui| {
+                let frozen_output =
+                    self.build_table(ui, frozen_builder, &frozen, (scroll_offset, scroll_to_row));
+                let scrolling_output = ScrollArea::horizontal()
+                    .show(ui, |ui| {
+                        self.build_table(
+                            ui,
+                            table_builder,
+                            &scrolling,
+                            (scroll_offset, scroll_to_row),
+                        )
+                    })
+                    .inner;
 
-                if self.drag_started_on.is_some() {
-                    if let Some(offset) = self.auto_scroll.start_scroll(max_rect, pointer) {
-                        table = table.vertical_scroll_offset(offset);
-                        ctx.request_repaint();
-                    }
-                };
+                if self.sync_frozen_scroll(
+                    frozen_output.state.offset.y,
+                    scrolling_output.state.offset.y,
+                ) {
+                    ctx.request_repaint();
+                }
+                scrolling_output
+            })
+            .inner
+        } else {
+            let part = TablePart {
+                columns: self.visible_columns(),
+                serial_column: self.add_serial_column,
+                id_salt: None,
+                hide_scroll_bar: false,
+            };
 
-                let output = table
-                    .header(20.0, |header| {
-                        self.build_head(header);
+            if self.horizontal_scroll {
+                ScrollArea::horizontal()
+                    .show(ui, |ui| {
+                        self.build_table(ui, table_builder, &part, (scroll_offset, scroll_to_row))
                     })
-                    .body(|body| {
-                        body.rows(25.0, self.formatted_rows.len(), |row| {
-                            let index = row.index();
-                            self.build_body(row, index);
-                        });
-                    });
-                let scroll_offset = output.state.offset.y;
-                self.update_scroll_offset(scroll_offset);
-            });
-        } else {
-            let mut table = TableBuilder::new(ui);
+                    .inner
+            } else {
+                self.build_table(ui, table_builder, &part, (scroll_offset, scroll_to_row))
+            }
+        };
+
+        self.update_scroll_offset(output.state.offset.y);
+        self.reset_column_widths = false;
+
+        if ctx.input(|i| i.pointer.primary_released()) {
+            self.dragged_column = None;
+        }
+
+        // Used for moving the focused cell by a page
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let rows_per_page = (output.inner_rect.height() / self.row_height) as usize;
+        self.rows_per_page = rows_per_page;
+
+        self.take_response()
+    }
+
+    /// Build one `TableBuilder` with the columns of the part and returns its scroll output
+    fn build_table<Builder>(
+        &mut self,
+        ui: &mut Ui,
+        table_builder: Builder,
+        part: &TablePart<F>,
+        (scroll_offset, scroll_to_row): (Option<f32>, Option<usize>),
+    ) -> ScrollAreaOutput<()>
+    where
+        Builder: FnOnce(TableBuilder) -> TableBuilder,
+    {
+        let mut table = TableBuilder::new(ui);
 
-            if self.add_serial_column {
-                table = table.column(Column::initial(25.0).clip(true));
+        if part.serial_column {
+            table = table.column(Column::initial(25.0).clip(true));
+        }
+
+        table = table_builder(table);
+
+        for column in &part.columns {
+            if let Some(layout) = column.column_layout() {
+                table = table.column(layout);
             }
+        }
 
-            table = table_builder(table);
+        if let Some(id_salt) = part.id_salt {
+            table = table.id_salt(id_salt);
+        }
 
-            if self.drag_started_on.is_some() {
-                if let Some(offset) = self.auto_scroll.start_scroll(max_rect, pointer) {
-                    table = table.vertical_scroll_offset(offset);
-                    ctx.request_repaint();
-                }
-            };
+        if part.hide_scroll_bar {
+            // Shrink to the width of the frozen columns and let the other part show the scrollbar
+            table = table
+                .auto_shrink([true, false])
+                .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden);
+        }
 
-            let output = table
-                .header(20.0, |header| {
-                    self.build_head(header);
-                })
-                .body(|body| {
-                    body.rows(25.0, self.formatted_rows.len(), |row| {
+        if let Some(offset) = scroll_offset {
+            table = table.vertical_scroll_offset(offset);
+        }
+
+        if let Some(index) = scroll_to_row {
+            table = table.scroll_to_row(index, None);
+        }
+
+        self.apply_column_width_reset(&table);
+
+        table
+            .header(self.header_height, |header| {
+                self.build_head(header, part);
+            })
+            .body(|body| {
+                if let Some(row_heights) = self.displayed_row_heights() {
+                    body.heterogeneous_rows(row_heights.into_iter(), |row| {
                         let index = row.index();
-                        self.build_body(row, index);
+                        self.build_body(row, index, part);
                     });
-                });
-            let scroll_offset = output.state.offset.y;
-            self.update_scroll_offset(scroll_offset);
-        }
+                } else {
+                    body.rows(self.row_height, self.formatted_rows.len(), |row| {
+                        let index = row.index();
+                        self.build_body(row, index, part);
+                    });
+                }
+            })
+    }
+
+    /// The height of each displayed row if `row_height_fn` is set
+    fn displayed_row_heights(&self) -> Option<Vec<f32>> {
+        let row_height_fn = self.row_height_fn.as_ref()?;
+        let row_heights = self
+            .formatted_rows
+            .iter()
+            .map(|id| {
+                self.rows
+                    .get(&id)
+                    .map_or(self.row_height, |row| row_height_fn(&row.row_data))
+            })
+            .collect();
+        Some(row_heights)
     }
 
-    fn build_head(&mut self, mut header: TableRow) {
-        if self.add_serial_column {
+    fn build_head(&mut self, mut header: TableRow, part: &TablePart<F>) {
+        if part.serial_column {
             header.col(|ui| {
                 ui.add_sized(ui.available_size(), Label::new(""));
             });
         }
-        for column_name in &self.all_columns.clone() {
+        for column_name in &part.columns {
             header.col(|ui| {
-                let sort_order = if &self.sorted_by == column_name {
-                    Some(self.sort_order)
-                } else {
-                    None
-                };
+                let sort_order = self
+                    .sort_stack
+                    .iter()
+                    .position(|(column, _)| column == column_name)
+                    .map(|position| (position, self.sort_stack[position].1));
+
+                let header_resp = column_name.create_header(ui, sort_order, self);
+                self.paint_rebuild_spinner(ui, column_name);
 
-                let Some(resp) = column_name.create_header(ui, sort_order, self) else {
+                let Some(mut resp) = header_resp else {
                     return;
                 };
 
+                if self.column_reordering {
+                    resp = resp.interact(Sense::drag());
+                    self.handle_header_drag(ui, &resp, column_name);
+                }
+
+                if self.column_visibility_menu {
+                    resp.context_menu(|ui| self.column_visibility_ui(ui));
+                }
+
                 // Response click sense is not forced. So if a header should not be used
                 // for sorting, without click there won't be any actions.
 
                 if resp.clicked() {
-                    let is_selected = &self.sorted_by == column_name;
-                    if is_selected {
-                        self.change_sort_order();
+                    let is_shift_pressed = ui.ctx().input(|i| i.modifiers.shift);
+                    if is_shift_pressed {
+                        self.add_sorted_by(column_name);
                     } else {
                         self.change_sorted_by(column_name);
                     }
                     self.recreate_rows();
+
+                    self.push_event(TableEvent::HeaderClicked {
+                        column: column_name.clone(),
+                    });
+                    if let Some((_, sort_order)) = self
+                        .sort_stack
+                        .iter()
+                        .find(|(column, _)| column == column_name)
+                    {
+                        self.push_event(TableEvent::SortChanged {
+                            column: column_name.clone(),
+                            sort_order: *sort_order,
+                        });
+                    }
                 }
             });
         }
     }
 
-    fn build_body(&mut self, mut row: TableRow, index: usize) {
-        let row_data = self.formatted_rows[index].clone();
+    fn build_body(&mut self, mut row: TableRow, index: usize, part: &TablePart<F>) {
+        // Rows can get removed while the body is being built
+        let Some(id) = self.formatted_rows.id_at(index) else {
+            return;
+        };
 
-        if self.add_serial_column {
+        if part.serial_column {
             row.col(|ui| {
                 ui.add_sized(ui.available_size(), Label::new(format!("{}", index + 1)));
             });
         }
-        self.handle_table_body(row, &row_data);
+        self.handle_table_body(row, id, &part.columns);
     }
 
-    /// Modify or add rows to the table. Changes are not immediately reflected in the UI.
-    /// You must call [`recreate_rows`](#method.recreate_rows) to apply these changes visually.
+    /// Modify or add rows to the table. Changes to existing rows are shown right away, but new
+    /// rows and the new position of modified rows only after calling
+    /// [`recreate_rows`](#method.recreate_rows).
+    ///
+    /// Rows removed in the closure are removed from the displayed rows right away. With the
+    /// [`undo_history`](#method.undo_history) enabled, the rows modified or removed in the closure
+    /// and the returned row are recorded as a single action.
     ///
     /// # Parameters:
     /// - `table`: A closure that takes a mutable reference to the rows and optionally returns a new row.
@@ -487,7 +920,13 @@ where
     ///
     /// # Auto Reload:
     /// - Use [`auto_reload`](#method.auto_reload) to automatically refresh the UI after a specified
-    ///   number of row modifications or additions.
+    ///   number of row modifications or additions, or [`reload_policy`](#method.reload_policy) to
+    ///   refresh it based on time as well.
+    ///
+    /// # Row Keys:
+    /// - With [`row_keys`](#method.row_keys) enabled, a returned row whose [`RowKey`] is already
+    ///   used replaces the data of that row instead of being added, and the ID of that row is
+    ///   returned.
     ///
     /// # Returns
     /// * `Option<i64>` - The row id that is used internally for the new row
@@ -495,7 +934,7 @@ where
     /// # Example:
     /// ```rust,ignore
     /// let new_row_id = table.add_modify_row(|rows| {
-    ///     let my_row = rows.get_mut(row_id).unwrap();
+    ///     let my_row = rows.get_mut(&row_id).unwrap();
     ///     // modify your row as necessary
     ///
     ///     let new_row = MyRow {
@@ -506,24 +945,23 @@ where
     /// ```
     pub fn add_modify_row<Fn>(&mut self, table: Fn) -> Option<i64>
     where
-        Fn: FnOnce(&mut HashMap<i64, SelectableRow<Row, F>>) -> Option<Row>,
+        Fn: FnOnce(&mut RowsMut<Row>) -> Option<Row>,
     {
-        let new_row = table(&mut self.rows);
-
-        let mut to_return = None;
+        let keep_before = self.is_recording_history() || self.key_index.is_some();
+        let mut rows = RowsMut::new(Arc::make_mut(&mut self.rows), keep_before);
+        let new_row = table(&mut rows);
 
-        if let Some(row) = new_row {
-            let selected_columns = HashSet::new();
-            let new_row = SelectableRow {
-                row_data: row,
-                id: self.last_id_used,
-                selected_columns,
-            };
-            to_return = Some(self.last_id_used);
-            self.rows.insert(new_row.id, new_row);
-            self.last_id_used += 1;
+        let changes = rows.into_changes();
+        self.start_placement_batch();
+        let modified = self.apply_rows_changes(changes, true);
+        for id in modified {
+            self.place_row(id);
         }
 
+        let to_return = new_row.map(|row| self.store_row(row));
+        self.end_placement_batch();
+        self.commit_history();
+
         let reload = self.auto_reload.increment_count();
 
         if reload {
@@ -532,76 +970,352 @@ where
         to_return
     }
 
-    /// Modify only the rows currently displayed in the UI.
+    /// Adds multiple rows to the table at once. Changes are not immediately reflected in the UI.
+    /// You must call [`recreate_rows`](#method.recreate_rows) to apply these changes visually.
+    ///
+    /// # Parameters:
+    /// - `rows`: The rows to add.
+    ///
+    /// # Auto Reload:
+    /// - The whole batch counts once toward the [`auto_reload`](#method.auto_reload) count.
+    ///
+    /// # Row Keys:
+    /// - With [`row_keys`](#method.row_keys) enabled, a row whose [`RowKey`] is already used
+    ///   replaces the data of that row, the same way as [`add_modify_row`](#method.add_modify_row).
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The row id of each row, in the order they were given
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let row_ids = table.extend_rows(received_rows);
+    /// ```
+    pub fn extend_rows<I>(&mut self, rows: I) -> Vec<i64>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        self.start_placement_batch();
+        let ids = rows.into_iter().map(|row| self.store_row(row)).collect();
+        self.end_placement_batch();
+
+        // The whole batch is undone at once
+        self.commit_history();
+
+        let reload = self.auto_reload.increment_count();
+
+        if reload {
+            self.recreate_rows();
+        }
+        ids
+    }
+
+    /// Store the row, replacing the row with the same key if row keys are enabled, and return the
+    /// ID of the row
+    fn store_row(&mut self, row: Row) -> i64 {
+        if let Some(existing_id) = self.keyed_row_id(&row) {
+            self.replace_row_data(existing_id, row);
+            existing_id
+        } else {
+            self.insert_row(row)
+        }
+    }
+
+    /// Store a new row with the next ID and return the ID
+    fn insert_row(&mut self, row: Row) -> i64 {
+        let new_row = SelectableRow {
+            row_data: row,
+            id: self.last_id_used,
+        };
+        let id = self.last_id_used;
+        if self.is_recording_history() {
+            self.record_change(id, None, Some(new_row.row_data.clone()));
+        }
+        self.index_row(&new_row.row_data, id);
+        Arc::make_mut(&mut self.rows).insert(id, Arc::new(new_row));
+        self.last_id_used += 1;
+        self.place_row(id);
+        id
+    }
+
+    /// Replace the data of an existing row. Returns `false` if no row has the ID or if the new
+    /// data would take the key of another row.
+    fn replace_row_data(&mut self, id: i64, row: Row) -> bool {
+        let Some(stored_row) = self.rows.get(&id).cloned() else {
+            return false;
+        };
+        if let Err(error) = self.reindex_row(&stored_row.row_data, &row, id) {
+            self.push_event(TableEvent::UpdateRejected { row_id: id, error });
+            return false;
+        }
+        // Dropping the clone first keeps `make_mut` from copying the row
+        drop(stored_row);
+
+        let Some(stored_row) = Arc::make_mut(&mut self.rows).get_mut(&id) else {
+            return false;
+        };
+        let previous_data = std::mem::replace(&mut Arc::make_mut(stored_row).row_data, row.clone());
+
+        if self.is_recording_history() {
+            self.record_change(id, Some(previous_data), Some(row));
+        }
+        self.place_row(id);
+        true
+    }
+
+    /// Removes a row from the table by its ID. The displayed rows and the selection are updated
+    /// immediately, without having to call [`recreate_rows`](#method.recreate_rows).
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row to remove.
+    ///
+    /// # Returns
+    /// * `Option<Row>` - The data of the removed row, if a row with the ID existed
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let removed_row = table.remove_row(row_id);
+    /// ```
+    pub fn remove_row(&mut self, id: i64) -> Option<Row> {
+        self.remove_rows([id]).pop()
+    }
+
+    /// Removes multiple rows from the table by their IDs. The displayed rows and the selection
+    /// are updated immediately, without having to call [`recreate_rows`](#method.recreate_rows).
+    ///
+    /// # Parameters:
+    /// - `ids`: The IDs of the rows to remove. IDs that are not in the table are ignored.
+    ///
+    /// # Returns
+    /// * `Vec<Row>` - The data of the removed rows, in the order of the given IDs
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let removed_rows = table.remove_rows(vec![1, 2, 3]);
+    /// ```
+    pub fn remove_rows<I>(&mut self, ids: I) -> Vec<Row>
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        let mut removed_ids = HashSet::new();
+        let mut removed_rows = Vec::new();
+
+        for id in ids {
+            if let Some(row_data) = self.take_stored_row(id) {
+                removed_ids.insert(id);
+                removed_rows.push(row_data);
+            }
+        }
+
+        if !removed_ids.is_empty() {
+            self.commit_history();
+            self.remove_displayed_rows(&removed_ids);
+        }
+        removed_rows
+    }
+
+    /// Remove the row from the stored rows and the key index and record the removal, without
+    /// committing it or updating the displayed rows
+    pub(crate) fn take_stored_row(&mut self, id: i64) -> Option<Row> {
+        let row = Arc::make_mut(&mut self.rows).remove(&id)?;
+        if self.is_recording_history() {
+            self.record_change(id, Some(row.row_data.clone()), None);
+        }
+        self.unindex_row(&row.row_data, id);
+        Some(Arc::unwrap_or_clone(row).row_data)
+    }
+
+    /// Removes all rows that have at least one selected cell. The displayed rows are updated
+    /// immediately, without having to call [`recreate_rows`](#method.recreate_rows).
+    ///
+    /// # Returns
+    /// * `Vec<Row>` - The data of the removed rows, in the order they were displayed
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let removed_rows = table.remove_selected_rows();
+    /// ```
+    pub fn remove_selected_rows(&mut self) -> Vec<Row> {
+        let selected_ids: Vec<i64> = self
+            .selected_rows()
+            .filter_map(|(index, _)| self.formatted_rows.id_at(index))
+            .collect();
+
+        self.remove_rows(selected_ids)
+    }
+
+    /// Removes the rows from the displayed rows and the selection, and shifts the index of the
+    /// rows that were after the removed ones
+    pub(crate) fn remove_displayed_rows(&mut self, removed_ids: &HashSet<i64>) {
+        if !removed_ids
+            .iter()
+            .any(|id| self.formatted_rows.contains(*id))
+        {
+            return;
+        }
+
+        if removed_ids.iter().any(|id| self.is_row_selected(*id)) {
+            self.mark_selection_changed();
+        }
+        let selection = self.take_selection();
+
+        for id in removed_ids {
+            self.formatted_rows.remove(*id);
+        }
+
+        self.restore_selection(selection);
+    }
+
+    /// Update the key index and the displayed rows after the rows were changed in a closure,
+    /// recording the changes in the undo history with `record`. Returns the IDs of the modified
+    /// rows that still exist.
+    fn apply_rows_changes(&mut self, changes: RowsChanges<Row>, record: bool) -> HashSet<i64> {
+        let RowsChanges {
+            modified,
+            removed,
+            mut before,
+        } = changes;
+
+        for id in &modified {
+            let Some(previous_data) = before.remove(id) else {
+                continue;
+            };
+            let Some(row_data) = self.rows.get(id).map(|row| row.row_data.clone()) else {
+                continue;
+            };
+            self.unindex_row(&previous_data, *id);
+            self.index_row(&row_data, *id);
+            if record {
+                self.record_change(*id, Some(previous_data), Some(row_data));
+            }
+        }
+
+        if !removed.is_empty() {
+            let mut removed_ids = HashSet::new();
+            for row in removed {
+                let previous_data = before
+                    .remove(&row.id)
+                    .unwrap_or_else(|| row.row_data.clone());
+                self.unindex_row(&previous_data, row.id);
+                if record {
+                    self.record_change(row.id, Some(previous_data), None);
+                }
+                removed_ids.insert(row.id);
+            }
+            self.remove_displayed_rows(&removed_ids);
+        }
+
+        modified
+    }
+
+    /// Modify the rows currently displayed in the UI.
     ///
     /// # Important:
     /// - This does not require calling `recreate_rows` to reflect changes.
-    /// - Should not be used when rows are frequently recreated, as data might be lost.
+    /// - The rows are modified where they are stored, so the changes are kept when the rows are
+    ///   recreated. Their position in the sort order is only updated on recreation.
     /// - Does not contribute toward `auto_reload` count.
+    /// - Rows removed in the closure are removed from the displayed rows right away.
     ///
     /// # Parameters:
-    /// - `table`: A closure that takes a mutable reference to the currently formatted rows and an index map.
+    /// - `table`: A closure that takes a mutable reference to the rows and the displayed rows,
+    ///   which give the display index of a row ID and the other way around.
     ///
     /// # Example:
     /// ```rust,ignore
-    /// table.modify_shown_row(|formatted_rows, indexed_ids| {
-    /// let row_id = 0;
-    /// let target_index = indexed_ids.get(row_id).unwrap();
-    /// let row = formatted_rows.get_mut(target_index).unwrap();
-    /// /* modify rows */
-    ///
+    /// table.modify_shown_row(|rows, displayed_rows| {
+    ///     for row_id in displayed_rows.iter() {
+    ///         let row = rows.get_mut(&row_id).unwrap();
+    ///         /* modify rows */
+    ///     }
     /// });
     /// ```
     pub fn modify_shown_row<Fn>(&mut self, mut rows: Fn)
     where
-        Fn: FnMut(&mut Vec<SelectableRow<Row, F>>, &HashMap<i64, usize>),
+        Fn: FnMut(&mut RowsMut<Row>, &DisplayedRows),
     {
-        rows(&mut self.formatted_rows, &self.indexed_ids);
+        let keep_before = self.key_index.is_some();
+        let mut table_rows = RowsMut::new(Arc::make_mut(&mut self.rows), keep_before);
+        rows(&mut table_rows, &self.formatted_rows);
+
+        let changes = table_rows.into_changes();
+        self.apply_rows_changes(changes, false);
     }
 
-    /// Sort the rows to the current sorting order and column and save them for later reuse
+    /// Filter the rows with the active search and column filters, sort them to the current sorting
+    /// order and column and save their IDs for later reuse
     fn sort_rows(&mut self) {
-        let mut row_data: Vec<SelectableRow<Row, F>> =
-            self.rows.par_iter().map(|(_, v)| v.clone()).collect();
-
-        row_data.par_sort_by(|a, b| {
-            let ordering = self.sorted_by.order_by(&a.row_data, &b.row_data);
-            match self.sort_order {
-                SortOrder::Ascending => ordering,
-                SortOrder::Descending => ordering.reverse(),
-            }
-        });
+        let query = self.row_query();
 
-        let indexed_data = row_data
-            .par_iter()
-            .enumerate()
-            .map(|(index, row)| (row.id, index))
-            .collect();
+        let mut row_data = filter_rows::<Row, F, Conf>(&self.rows, &query);
+        sort_filtered_rows(&mut row_data, &query.sort_stack);
+        self.formatted_rows = row_data.iter().map(|row| row.id).collect();
+        self.sort_outdated = false;
+    }
 
-        self.indexed_ids = indexed_data;
-        self.formatted_rows = row_data;
+    /// Sort by the provided column only. If it was already the primary sort key, the sort order
+    /// is flipped.
+    fn change_sorted_by(&mut self, sort_by: &F) {
+        let sort_order = match self.sort_stack.first() {
+            Some((column, sort_order)) if column == sort_by => sort_order.reversed(),
+            _ => SortOrder::default(),
+        };
+        self.sort_stack = vec![(sort_by.clone(), sort_order)];
     }
 
-    /// Change the current sort order from ascending to descending and vice versa. Will unselect
-    /// all selected rows
-    fn change_sort_order(&mut self) {
-        self.unselect_all();
-        if matches!(self.sort_order, SortOrder::Ascending) {
-            self.sort_order = SortOrder::Descending;
+    /// Add the provided column as the next sort key. If it is already in the sort stack, its
+    /// sort order is flipped instead.
+    fn add_sorted_by(&mut self, sort_by: &F) {
+        if let Some((_, sort_order)) = self
+            .sort_stack
+            .iter_mut()
+            .find(|(column, _)| column == sort_by)
+        {
+            *sort_order = sort_order.reversed();
         } else {
-            self.sort_order = SortOrder::Ascending;
+            self.sort_stack
+                .push((sort_by.clone(), SortOrder::default()));
         }
     }
 
-    /// Change the column that is currently being used for sorting. Will unselect all rows
-    fn change_sorted_by(&mut self, sort_by: &F) {
-        self.unselect_all();
-        self.sorted_by = sort_by.clone();
-        self.sort_order = SortOrder::default();
+    /// Returns the columns the table is currently sorted by, in order of priority.
+    ///
+    /// # Returns:
+    /// - `&[(F, SortOrder)]`: The sort stack, the first entry being the primary sort key.
+    pub fn sort_stack(&self) -> &[(F, SortOrder)] {
+        &self.sort_stack
+    }
+
+    /// Replaces the sort stack and recreates the displayed rows. Useful for restoring a saved
+    /// sort state.
+    ///
+    /// # Parameters:
+    /// - `sort_stack`: The columns to sort by in order of priority. If empty, the table is sorted
+    ///   by the default column in ascending order.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_sort_stack(vec![
+    ///     (Column::Name, SortOrder::Ascending),
+    ///     (Column::Age, SortOrder::Descending),
+    /// ]);
+    /// ```
+    pub fn set_sort_stack(&mut self, sort_stack: Vec<(F, SortOrder)>) {
+        if sort_stack.is_empty() {
+            self.sort_stack = vec![(F::default(), SortOrder::default())];
+        } else {
+            self.sort_stack = sort_stack;
+        }
+        self.recreate_rows();
     }
 
     /// Recreates the rows shown in the UI for the next frame load.
     ///
+    /// Selected cells stay selected as long as their row is still displayed after the
+    /// recreation, even if the row moved to a different position.
+    ///
+    /// With [`async_rebuild`](#method.async_rebuild) enabled, the rows are filtered and sorted in
+    /// the background and the previous rows stay displayed until the result is ready.
+    ///
     /// # Performance:
     /// - Should be used sparingly for large datasets as frequent calls can lead to performance issues.
     /// - Consider calling after every X amount row updates, based on how frequently new rows are being
@@ -612,20 +1326,15 @@ where
     /// table.recreate_rows();
     /// ```
     pub fn recreate_rows(&mut self) {
+        self.auto_reload.mark_reloaded();
+        if self.start_async_rebuild() {
+            return;
+        }
+
+        let selection = self.take_selection();
         self.formatted_rows.clear();
-        self.active_rows.clear();
-        self.active_columns.clear();
         self.sort_rows();
-    }
-
-    /// The first column that was passed by the user
-    fn first_column(&self) -> F {
-        self.all_columns[0].clone()
-    }
-
-    /// The last column that was passed by the user
-    fn last_column(&self) -> F {
-        self.all_columns[self.all_columns.len() - 1].clone()
+        self.restore_selection(selection);
     }
 
     /// Convert a number to a column value
@@ -636,32 +1345,32 @@ where
             .expect("Not in the column list")
     }
 
-    /// Get the next column of the provided column
-    fn next_column(&self, column: &F) -> F {
-        let current_column_num = self.column_to_num(column);
-        if current_column_num == self.all_columns.len() - 1 {
-            self.all_columns[0].clone()
-        } else {
-            self.all_columns[current_column_num + 1].clone()
-        }
-    }
-
-    /// Get the previous column of the provided column
-    fn previous_column(&self, column: &F) -> F {
-        let current_column_num = self.column_to_num(column);
-        if current_column_num == 0 {
-            self.all_columns[self.all_columns.len() - 1].clone()
-        } else {
-            self.all_columns[current_column_num - 1].clone()
-        }
-    }
-
     /// Builds the table's Body section
-    fn handle_table_body(&mut self, mut row: TableRow, row_data: &SelectableRow<Row, F>) {
-        for column_name in &self.all_columns.clone() {
+    fn handle_table_body(&mut self, mut row: TableRow, row_id: i64, columns: &[F]) {
+        for column_name in columns {
             row.col(|ui| {
-                let selected = row_data.selected_columns.contains(column_name);
-                let mut resp = column_name.create_table_row(ui, row_data, selected, self);
+                if self.is_editing(row_id, column_name) {
+                    self.show_cell_editor(ui, row_id, column_name);
+                    return;
+                }
+
+                let selected = self.is_cell_selected(row_id, column_name);
+                let focused = self
+                    .focused_cell
+                    .as_ref()
+                    .is_some_and(|(id, column)| *id == row_id && column == column_name);
+
+                // Only the pointer to the row is cloned, as the table is borrowed mutably by the
+                // cell. A cell modifying its own row copies it, the other rows are not copied.
+                let Some(row_data) = self.rows.get(&row_id).cloned() else {
+                    return;
+                };
+                let mut resp = column_name.create_table_row(ui, &row_data, selected, focused, self);
+
+                // The row got removed while it was being created
+                if !self.formatted_rows.contains(row_id) {
+                    return;
+                }
 
                 // Drag sense is forced otherwise there is no point of this library.
                 resp = resp.interact(Sense::drag());
@@ -674,7 +1383,10 @@ where
                     {
                         self.unselect_all();
                     }
-                    self.drag_started_on = Some((row_data.id, column_name.clone()));
+                    self.drag_started_on = Some((row_id, column_name.clone()));
+                    self.drag_base = Some(self.selection.clone());
+                    self.focused_cell.clone_from(&self.drag_started_on);
+                    self.selection_anchor.clone_from(&self.drag_started_on);
                 }
 
                 let pointer_released = ui.input(|a| a.pointer.primary_released());
@@ -683,29 +1395,54 @@ where
                     self.last_active_row = None;
                     self.last_active_column = None;
                     self.drag_started_on = None;
+                    self.drag_base = None;
                     self.beyond_drag_point = false;
                 }
 
                 if resp.clicked() {
+                    self.push_event(TableEvent::CellClicked {
+                        row_id,
+                        column: column_name.clone(),
+                    });
+                }
+                if resp.double_clicked() {
+                    self.push_event(TableEvent::CellDoubleClicked {
+                        row_id,
+                        column: column_name.clone(),
+                    });
+                    self.start_editing(row_id, column_name);
+                }
+
+                let is_shift_pressed = ui.ctx().input(|i| i.modifiers.shift);
+
+                if resp.clicked() && is_shift_pressed && self.selection_anchor.is_some() {
+                    // Shift + click selects everything between the anchor and this cell. The
+                    // anchor stays the same so the range can be adjusted with another Shift + click
+                    if let Some(anchor) = self.selection_anchor.clone() {
+                        self.select_cell_range(&anchor, &(row_id, column_name.clone()));
+                    }
+                    self.focused_cell = Some((row_id, column_name.clone()));
+                } else if resp.clicked() {
                     // If CTRL is not pressed down and the mouse right click is not pressed, unselect all cells
                     if !ui.ctx().input(|i| i.modifiers.ctrl)
                         && !ui.ctx().input(|i| i.pointer.secondary_clicked())
                     {
                         self.unselect_all();
                     }
-                    self.select_single_row_cell(row_data.id, column_name);
+                    self.select_single_row_cell(row_id, column_name);
+                    self.focused_cell = Some((row_id, column_name.clone()));
+                    self.selection_anchor = Some((row_id, column_name.clone()));
                 }
 
                 if ui.ui_contains_pointer() && self.drag_started_on.is_some() {
                     if let Some(drag_start) = self.drag_started_on.as_ref() {
                         // Only call drag either when not on the starting drag row/column or went beyond the
                         // drag point at least once. Otherwise normal click would be considered as drag
-                        if drag_start.0 != row_data.id
+                        if drag_start.0 != row_id
                             || &drag_start.1 != column_name
                             || self.beyond_drag_point
                         {
-                            let is_ctrl_pressed = ui.ctx().input(|i| i.modifiers.ctrl);
-                            self.select_dragged_row_cell(row_data.id, column_name, is_ctrl_pressed);
+                            self.select_dragged_row_cell(row_id, column_name);
                         }
                     }
                 }
@@ -715,6 +1452,8 @@ where
 
     /// Returns the total number of rows currently being displayed in the UI.
     ///
+    /// Rows that do not match the active search or column filters are not counted.
+    ///
     /// # Returns:
     /// - `usize`: The number of rows that are formatted and ready for display.
     pub fn total_displayed_rows(&self) -> usize {
@@ -723,25 +1462,42 @@ where
 
     /// Returns the total number of rows in the table (both displayed and non-displayed).
     ///
+    /// Includes the rows that are hidden by the active search or column filters.
+    ///
     /// # Returns:
     /// - `usize`: The total number of rows stored in the table, regardless of whether they are being displayed or not.
     pub fn total_rows(&self) -> usize {
         self.rows.len()
     }
 
-    /// Provides a reference to the rows currently being displayed in the UI.
+    /// Provides the rows currently being displayed in the UI, in the order they are displayed.
     ///
     /// # Returns:
-    /// - `&Vec<SelectableRow<Row, F>>`: A reference to the vector of formatted rows ready for display.
-    pub const fn get_displayed_rows(&self) -> &Vec<SelectableRow<Row, F>> {
+    /// - `impl Iterator<Item = &SelectableRow<Row>>`: The displayed rows, borrowed from the stored
+    ///   rows.
+    pub fn get_displayed_rows(&self) -> impl Iterator<Item = &SelectableRow<Row>> {
+        self.formatted_rows
+            .iter()
+            .filter_map(|id| self.rows.get(&id))
+            .map(AsRef::as_ref)
+    }
+
+    /// Provides the IDs of the rows currently being displayed in the UI, in the order they are
+    /// displayed.
+    ///
+    /// # Returns:
+    /// - `&DisplayedRows`: The IDs of the displayed rows, with lookups of the display index of a
+    ///   row ID and the other way around.
+    pub const fn displayed_row_ids(&self) -> &DisplayedRows {
         &self.formatted_rows
     }
 
     /// Provides a reference to all rows in the table, regardless of whether they are displayed.
     ///
     /// # Returns:
-    /// - `&HashMap<i64, SelectableRow<Row, F>>`: A reference to the entire collection of rows in the table.
-    pub const fn get_all_rows(&self) -> &HashMap<i64, SelectableRow<Row, F>> {
+    /// - `&HashMap<i64, Arc<SelectableRow<Row>>>`: A reference to the entire collection of rows in
+    ///   the table.
+    pub fn get_all_rows(&self) -> &HashMap<i64, Arc<SelectableRow<Row>>> {
         &self.rows
     }
 
@@ -779,4 +1535,96 @@ where
         self.horizontal_scroll = true;
         self
     }
+
+    /// Sets the height of the header row. Default: 20.0
+    ///
+    /// # Parameters:
+    /// - `height`: The height of the header in pixels.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the new header height.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .header_height(30.0);
+    /// ```
+    #[must_use]
+    pub const fn header_height(mut self, height: f32) -> Self {
+        self.header_height = height;
+        self
+    }
+
+    /// Sets the height of each body row. Default: 25.0
+    ///
+    /// # Parameters:
+    /// - `height`: The height of each row in pixels.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the new row height.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .row_height(40.0);
+    /// ```
+    #[must_use]
+    pub const fn row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Calculates the height of each body row from its data, allowing rows with multiline text or
+    /// images to be taller than the others. Overrides the height set with
+    /// [`row_height`](#method.row_height), which is still used for moving the focused cell by a page.
+    ///
+    /// # Performance:
+    /// - The closure is called for every displayed row on each frame, so it should be cheap.
+    ///
+    /// # Parameters:
+    /// - `row_height`: A closure that returns the height of the row in pixels.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with per-row heights.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .row_height_fn(|row| if row.thumbnail.is_some() { 80.0 } else { 25.0 });
+    /// ```
+    #[must_use]
+    pub fn row_height_fn<H>(mut self, row_height: H) -> Self
+    where
+        H: Fn(&Row) -> f32 + Send + Sync + 'static,
+    {
+        self.row_height_fn = Some(Box::new(row_height));
+        self
+    }
+
+    /// Sets the height of the header row at runtime.
+    ///
+    /// # Parameters:
+    /// - `height`: The height of the header in pixels.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_header_height(30.0);
+    /// ```
+    pub const fn set_header_height(&mut self, height: f32) {
+        self.header_height = height;
+    }
+
+    /// Sets the height of each body row at runtime. Has no effect on the displayed rows if a
+    /// per-row height closure is set.
+    ///
+    /// # Parameters:
+    /// - `height`: The height of each row in pixels.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_row_height(40.0);
+    /// ```
+    pub const fn set_row_height(&mut self, height: f32) {
+        self.row_height = height;
+    }
 }
@@ -0,0 +1,176 @@
+use egui::{Event, Ui};
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable, TableEvent};
+
+/// Functions related to pasting clipboard text into the cells
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Allows pasting clipboard text into the table with Ctrl+V. The values are passed to
+    /// [`ColumnOperations::set_from_text`] of their column.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with pasting enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .allow_paste();
+    /// ```
+    #[must_use]
+    pub const fn allow_paste(mut self) -> Self {
+        self.allow_paste = true;
+        self
+    }
+
+    /// Sets whether clipboard text can be pasted into the table with Ctrl+V.
+    ///
+    /// # Parameters:
+    /// - `status`: `true` to enable pasting, `false` to disable it.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_allow_paste(true);
+    /// ```
+    pub const fn set_allow_paste(&mut self, status: bool) {
+        self.allow_paste = status;
+    }
+
+    /// Pastes tab and newline separated text into the displayed cells, starting from the focused
+    /// cell or, without one, the top-left selected cell. Each line goes into the next displayed
+    /// row and each tab separated value into the next visible column. Values that fall outside
+    /// the table are ignored.
+    ///
+    /// The values are parsed with [`ColumnOperations::set_from_text`], checked by the
+    /// [`edit_validator`](#method.edit_validator), if any, and the rows are updated right away.
+    /// The row order is kept until the rows are recreated. The number of updated cells is reported
+    /// with [`TableEvent::Pasted`] and each value that was not accepted with
+    /// [`TableEvent::PasteFailed`].
+    ///
+    /// # Parameters:
+    /// - `text`: The text to paste, such as cells copied from a spreadsheet.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.paste_text("1\tfirst\n2\tsecond");
+    /// ```
+    pub fn paste_text(&mut self, text: &str) {
+        let Some((start_row, start_column)) = self.paste_start() else {
+            return;
+        };
+
+        let visible_columns = self.visible_columns();
+        let mut updated = 0;
+
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let lines: Vec<&str> = text.split('\n').collect();
+
+        // The rows are picked before pasting as the incremental sort can move them
+        let target_ids: Vec<i64> = self
+            .formatted_rows
+            .iter_from(start_row)
+            .take(lines.len())
+            .collect();
+
+        self.start_placement_batch();
+        for (id, line) in target_ids.into_iter().zip(lines) {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            for (column_offset, value) in line.split('\t').enumerate() {
+                let Some(column) = visible_columns.get(start_column + column_offset) else {
+                    break;
+                };
+
+                match self.set_cell_text(id, column, value) {
+                    Ok(()) => updated += 1,
+                    Err(error) => self.push_event(TableEvent::PasteFailed {
+                        row_id: id,
+                        column: column.clone(),
+                        error,
+                    }),
+                }
+            }
+        }
+
+        self.end_placement_batch();
+
+        // The whole paste is undone at once
+        self.commit_history();
+        self.push_event(TableEvent::Pasted { updated });
+    }
+
+    /// Paste the clipboard text if pasting is enabled and no other widget is using the keyboard
+    pub(crate) fn handle_paste(&mut self, ui: &Ui) {
+        if !self.allow_paste || ui.ctx().wants_keyboard_input() {
+            return;
+        }
+
+        let pasted_text = ui.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            })
+        });
+
+        if let Some(text) = pasted_text {
+            self.paste_text(&text);
+        }
+    }
+
+    /// Parse the text with the column, check it with the edit validator and update the row. The
+    /// row is left untouched if either fails. The change is recorded in the history but the action
+    /// must be committed by the caller.
+    pub(crate) fn set_cell_text(&mut self, id: i64, column: &F, text: &str) -> Result<(), String> {
+        let Some(row) = self.rows.get(&id) else {
+            return Err(String::from("The row no longer exists"));
+        };
+
+        let mut row_data = row.row_data.clone();
+        column.set_from_text(&mut row_data, text)?;
+        if let Some(validator) = &self.edit_validator {
+            validator(column, &row_data)?;
+        }
+        let previous_data = row.row_data.clone();
+        self.reindex_row(&previous_data, &row_data, id)?;
+
+        if self.is_recording_history() {
+            self.record_change(id, Some(previous_data), Some(row_data.clone()));
+        }
+
+        if let Some(row) = Arc::make_mut(&mut self.rows).get_mut(&id) {
+            Arc::make_mut(row).row_data = row_data;
+        }
+        self.place_row(id);
+        Ok(())
+    }
+
+    /// The display indices of the row and the visible column where pasting starts
+    fn paste_start(&self) -> Option<(usize, usize)> {
+        let visible_columns = self.visible_columns();
+
+        if let Some((id, column)) = &self.focused_cell {
+            let row_index = self.formatted_rows.index_of(*id)?;
+            let column_index = visible_columns.iter().position(|c| c == column)?;
+            return Some((row_index, column_index));
+        }
+
+        let (row_index, _) = self.selected_rows().next()?;
+        let selected_columns = self.selection.columns();
+        let column_index = visible_columns
+            .iter()
+            .position(|column| selected_columns.contains(column))?;
+        Some((row_index, column_index))
+    }
+}
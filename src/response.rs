@@ -0,0 +1,171 @@
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable, SortOrder};
+
+/// An event that happened in the table, reported through [`TableResponse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableEvent<F> {
+    /// The selected cells changed.
+    SelectionChanged,
+    /// A header was clicked and the sort order of the column changed.
+    SortChanged { column: F, sort_order: SortOrder },
+    /// A cell was clicked.
+    CellClicked { row_id: i64, column: F },
+    /// A cell was double-clicked.
+    CellDoubleClicked { row_id: i64, column: F },
+    /// The header of a column was clicked.
+    HeaderClicked { column: F },
+    /// The selected cells were copied to the clipboard.
+    CopyPerformed,
+    /// The order of the columns changed. The new order can be read with
+    /// [`column_order`](SelectableTable::column_order).
+    ColumnOrderChanged,
+    /// Clipboard text was pasted into the table and the given number of cells were updated.
+    Pasted { updated: usize },
+    /// A pasted value could not be parsed by the column and the cell was left unchanged.
+    PasteFailed {
+        row_id: i64,
+        column: F,
+        error: String,
+    },
+    /// The last recorded row change was undone.
+    Undone,
+    /// The last undone row change was redone.
+    Redone,
+    /// An inline edit of a cell was committed and stored.
+    CellEdited { row_id: i64, column: F },
+    /// An inline edit could not be parsed or was rejected by the validator.
+    EditRejected {
+        row_id: i64,
+        column: F,
+        error: String,
+    },
+    /// An update of the row was rejected as it would give the row the key of another row.
+    UpdateRejected { row_id: i64, error: String },
+}
+
+/// The events that happened in the table since the previous call of
+/// [`show_ui`](SelectableTable::show_ui), in the order they happened.
+///
+/// # Example:
+/// ```rust,ignore
+/// let response = table.show_ui(ui, |builder| builder);
+///
+/// if let Some((row_id, _column)) = response.double_clicked_cell() {
+///     open_detail_panel(row_id);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TableResponse<F> {
+    /// All events of the frame
+    pub events: Vec<TableEvent<F>>,
+}
+
+impl<F> TableResponse<F> {
+    /// Whether the selected cells changed.
+    #[must_use]
+    pub fn selection_changed(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, TableEvent::SelectionChanged))
+    }
+
+    /// Whether the selected cells were copied to the clipboard.
+    #[must_use]
+    pub fn copy_performed(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, TableEvent::CopyPerformed))
+    }
+
+    /// The last cell that was clicked, if any.
+    ///
+    /// # Returns:
+    /// - `Option<(i64, &F)>`: The row ID and the column of the clicked cell.
+    #[must_use]
+    pub fn clicked_cell(&self) -> Option<(i64, &F)> {
+        self.events.iter().rev().find_map(|event| match event {
+            TableEvent::CellClicked { row_id, column } => Some((*row_id, column)),
+            _ => None,
+        })
+    }
+
+    /// The last cell that was double-clicked, if any.
+    ///
+    /// # Returns:
+    /// - `Option<(i64, &F)>`: The row ID and the column of the double-clicked cell.
+    #[must_use]
+    pub fn double_clicked_cell(&self) -> Option<(i64, &F)> {
+        self.events.iter().rev().find_map(|event| match event {
+            TableEvent::CellDoubleClicked { row_id, column } => Some((*row_id, column)),
+            _ => None,
+        })
+    }
+
+    /// The cells whose pasted value could not be parsed.
+    ///
+    /// # Returns:
+    /// - `Vec<(i64, &F, &str)>`: The row ID, the column and the parsing error of each cell.
+    #[must_use]
+    pub fn paste_failures(&self) -> Vec<(i64, &F, &str)> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                TableEvent::PasteFailed {
+                    row_id,
+                    column,
+                    error,
+                } => Some((*row_id, column, error.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The last sort change, if any.
+    ///
+    /// # Returns:
+    /// - `Option<(&F, SortOrder)>`: The column that was clicked and its new sort order.
+    #[must_use]
+    pub fn sort_changed(&self) -> Option<(&F, SortOrder)> {
+        self.events.iter().rev().find_map(|event| match event {
+            TableEvent::SortChanged { column, sort_order } => Some((column, *sort_order)),
+            _ => None,
+        })
+    }
+}
+
+/// Functions related to collecting the events of the table
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Record an event to be returned by the next `show_ui` call
+    pub(crate) fn push_event(&mut self, event: TableEvent<F>) {
+        self.events.push(event);
+    }
+
+    /// Record that the selection was modified
+    pub(crate) const fn mark_selection_changed(&mut self) {
+        self.selection_changed = true;
+    }
+
+    /// Take all the recorded events and reset them for the next frame
+    pub(crate) fn take_response(&mut self) -> TableResponse<F> {
+        let mut events = std::mem::take(&mut self.events);
+        if self.selection_changed {
+            self.selection_changed = false;
+            events.push(TableEvent::SelectionChanged);
+        }
+        TableResponse { events }
+    }
+}
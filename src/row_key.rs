@@ -0,0 +1,327 @@
+use egui::ahash::{HashMap, HashMapExt};
+use std::any::Any;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable};
+
+/// A stable key that identifies a row, such as a database primary key.
+///
+/// Implementing this trait allows the rows to be upserted, looked up, selected and removed by
+/// their own key instead of the row ID assigned by the table.
+///
+/// # Example:
+/// ```rust,ignore
+/// impl RowKey for MyRow {
+///     type Key = u64;
+///
+///     fn row_key(&self) -> Self::Key {
+///         self.database_id
+///     }
+/// }
+/// ```
+pub trait RowKey {
+    /// The type of the key
+    type Key: Eq + Hash + Clone + Send + Sync + 'static;
+
+    /// Returns the key of this row. Two rows in the same table must not share a key.
+    fn row_key(&self) -> Self::Key;
+}
+
+/// Maps the row keys to the row IDs without the table having to know the key type
+pub trait KeyIndex<Row>: Send + Sync {
+    /// Add the row to the index. An existing entry for the same key is kept.
+    fn insert(&mut self, row: &Row, id: i64);
+    /// Remove the row from the index if its key points to this ID
+    fn remove(&mut self, row: &Row, id: i64);
+    /// Remove all entries
+    fn clear(&mut self);
+    /// The ID of the row that has the same key as the given row
+    fn id_for_row(&self, row: &Row) -> Option<i64>;
+    /// The ID of the row with the key. The key must be of the `RowKey::Key` type of the row.
+    fn id_for_key(&self, key: &dyn Any) -> Option<i64>;
+}
+
+/// The key index of the rows implementing `RowKey`
+struct RowKeyIndex<K> {
+    ids: HashMap<K, i64>,
+}
+
+impl<Row> KeyIndex<Row> for RowKeyIndex<Row::Key>
+where
+    Row: RowKey,
+{
+    fn insert(&mut self, row: &Row, id: i64) {
+        self.ids.entry(row.row_key()).or_insert(id);
+    }
+
+    fn remove(&mut self, row: &Row, id: i64) {
+        let key = row.row_key();
+        if self.ids.get(&key) == Some(&id) {
+            self.ids.remove(&key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.ids.clear();
+    }
+
+    fn id_for_row(&self, row: &Row) -> Option<i64> {
+        self.ids.get(&row.row_key()).copied()
+    }
+
+    fn id_for_key(&self, key: &dyn Any) -> Option<i64> {
+        let key = key.downcast_ref::<Row::Key>()?;
+        self.ids.get(key).copied()
+    }
+}
+
+/// Functions related to keeping the key index in sync with the rows
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// The ID of the row that has the same key as the given row, if row keys are enabled
+    pub(crate) fn keyed_row_id(&self, row: &Row) -> Option<i64> {
+        self.key_index.as_ref()?.id_for_row(row)
+    }
+
+    /// Add a new row to the key index
+    pub(crate) fn index_row(&mut self, row: &Row, id: i64) {
+        if let Some(key_index) = self.key_index.as_mut() {
+            key_index.insert(row, id);
+        }
+    }
+
+    /// Remove a row from the key index
+    pub(crate) fn unindex_row(&mut self, row: &Row, id: i64) {
+        if let Some(key_index) = self.key_index.as_mut() {
+            key_index.remove(row, id);
+        }
+    }
+
+    /// Remove all rows from the key index
+    pub(crate) fn clear_key_index(&mut self) {
+        if let Some(key_index) = self.key_index.as_mut() {
+            key_index.clear();
+        }
+    }
+
+    /// Returns an error if the modified row would take the key of another row, otherwise moves the
+    /// row to its new key
+    pub(crate) fn reindex_row(&mut self, before: &Row, after: &Row, id: i64) -> Result<(), String> {
+        let Some(key_index) = self.key_index.as_mut() else {
+            return Ok(());
+        };
+
+        if key_index
+            .id_for_row(after)
+            .is_some_and(|existing_id| existing_id != id)
+        {
+            return Err(String::from("Another row already uses this key"));
+        }
+
+        key_index.remove(before, id);
+        key_index.insert(after, id);
+        Ok(())
+    }
+}
+
+/// Functions related to accessing the rows by their own key
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync + RowKey,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Keeps an index of the [`RowKey`] of each row for fast lookups by key.
+    ///
+    /// While enabled, adding a row whose key is already used replaces the data of the existing row
+    /// and keeps its ID, so a key always points to a single row. Inline edits and pastes that would
+    /// give a row the key of another row are rejected.
+    ///
+    /// # Considerations:
+    /// - Keys changed in place inside the [`add_modify_row`](#method.add_modify_row) or
+    ///   `modify_shown_row` closure are picked up once the closure returns, but are not checked
+    ///   against the keys of the other rows.
+    /// - Existing rows are indexed in the order of their IDs. If some of them share a key, the row
+    ///   with the lowest ID is the one found by the key.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with row keys enabled.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .row_keys();
+    /// ```
+    #[must_use]
+    pub fn row_keys(mut self) -> Self {
+        self.enable_row_keys();
+        self
+    }
+
+    /// Enables the [`RowKey`] index if it is not enabled yet.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.enable_row_keys();
+    /// ```
+    pub fn enable_row_keys(&mut self) {
+        if self.key_index.is_some() {
+            return;
+        }
+
+        let mut ids: Vec<i64> = self.rows.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut key_index = RowKeyIndex {
+            ids: HashMap::with_capacity(ids.len()),
+        };
+        for id in ids {
+            KeyIndex::insert(&mut key_index, &self.rows[&id].row_data, id);
+        }
+        self.key_index = Some(Box::new(key_index));
+    }
+
+    /// Adds the row or, if a row with the same key exists, replaces its data. Replaced rows are
+    /// updated in the displayed rows right away while new rows are shown once the rows are
+    /// recreated. Enables the key index if needed.
+    ///
+    /// # Auto Reload:
+    /// - Counts toward the [`auto_reload`](#method.auto_reload) count like
+    ///   [`add_modify_row`](#method.add_modify_row).
+    ///
+    /// # Parameters:
+    /// - `row`: The row to add or update.
+    ///
+    /// # Returns:
+    /// - `i64`: The ID of the added or updated row.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let row_id = table.upsert_row(row_from_database);
+    /// ```
+    pub fn upsert_row(&mut self, row: Row) -> i64 {
+        self.enable_row_keys();
+        self.add_modify_row(|_| Some(row))
+            .expect("a returned row is always stored")
+    }
+
+    /// Adds or updates multiple rows at once, each the same way as
+    /// [`upsert_row`](#method.upsert_row). Enables the key index if needed.
+    ///
+    /// # Auto Reload:
+    /// - The whole batch counts once toward the [`auto_reload`](#method.auto_reload) count.
+    ///
+    /// # Parameters:
+    /// - `rows`: The rows to add or update.
+    ///
+    /// # Returns:
+    /// - `Vec<i64>`: The ID of each added or updated row, in the order they were given.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let row_ids = table.upsert_rows(changed_rows);
+    /// ```
+    pub fn upsert_rows<I>(&mut self, rows: I) -> Vec<i64>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        self.enable_row_keys();
+        self.extend_rows(rows)
+    }
+
+    /// Returns the ID of the row with the key.
+    ///
+    /// # Parameters:
+    /// - `key`: The key of the row.
+    ///
+    /// # Returns:
+    /// - `Option<i64>`: The ID of the row, if a row with the key exists.
+    pub fn id_for_key(&self, key: &Row::Key) -> Option<i64> {
+        if let Some(key_index) = &self.key_index {
+            return key_index.id_for_key(key);
+        }
+
+        // Without the index the rows have to be searched, with the lowest ID winning
+        self.rows
+            .values()
+            .filter(|row| &row.row_data.row_key() == key)
+            .map(|row| row.id)
+            .min()
+    }
+
+    /// Returns the row with the key.
+    ///
+    /// # Parameters:
+    /// - `key`: The key of the row.
+    ///
+    /// # Returns:
+    /// - `Option<&SelectableRow<Row>>`: The row, if a row with the key exists.
+    pub fn get_row_by_key(&self, key: &Row::Key) -> Option<&SelectableRow<Row>> {
+        let id = self.id_for_key(key)?;
+        self.rows.get(&id).map(AsRef::as_ref)
+    }
+
+    /// Removes the row with the key. The displayed rows are updated immediately.
+    ///
+    /// # Parameters:
+    /// - `key`: The key of the row.
+    ///
+    /// # Returns:
+    /// - `Option<Row>`: The data of the removed row, if a row with the key existed.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.remove_row_by_key(&deleted_database_id);
+    /// ```
+    pub fn remove_row_by_key(&mut self, key: &Row::Key) -> Option<Row> {
+        let id = self.id_for_key(key)?;
+        self.remove_row(id)
+    }
+
+    /// Selects all visible columns of the displayed row with the key, keeping the current
+    /// selection.
+    ///
+    /// # Parameters:
+    /// - `key`: The key of the row.
+    ///
+    /// # Returns:
+    /// - `bool`: `false` if no displayed row has the key.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.select_row_by_key(&database_id);
+    /// ```
+    pub fn select_row_by_key(&mut self, key: &Row::Key) -> bool {
+        let Some(id) = self.id_for_key(key) else {
+            return false;
+        };
+        let Some(index) = self.formatted_rows.index_of(id) else {
+            return false;
+        };
+
+        let visible_columns = self.visible_columns();
+        self.selection.add_row(index, visible_columns);
+        self.mark_selection_changed();
+        true
+    }
+}
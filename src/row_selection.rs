@@ -1,8 +1,17 @@
-use egui::ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
+use egui::ahash::HashSet;
 use egui::Ui;
 use std::hash::Hash;
 
-use crate::{ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable};
+use crate::selection::IdSelection;
+use crate::{
+    ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable, Selection, TableEvent,
+};
+
+/// The selection and the selection at the start of the ongoing drag, keyed by the row IDs
+pub struct TakenSelection<F> {
+    selection: IdSelection<F>,
+    drag_base: Option<IdSelection<F>>,
+}
 
 /// Functions related to selection of rows and columns
 #[allow(clippy::too_many_lines)]
@@ -21,281 +30,202 @@ where
     Conf: Default,
 {
     pub(crate) fn select_single_row_cell(&mut self, id: i64, column_name: &F) {
-        self.active_columns.insert(column_name.clone());
-        self.active_rows.insert(id);
-
-        let target_index = self.indexed_ids.get(&id).expect("target_index not found");
+        let Some(index) = self.formatted_rows.index_of(id) else {
+            return;
+        };
+        self.mark_selection_changed();
 
         if self.select_full_row {
-            self.active_columns.extend(self.all_columns.clone());
-
-            self.formatted_rows
-                .get_mut(*target_index)
-                .expect("Row not found")
-                .selected_columns
-                .extend(self.all_columns.clone());
+            self.selection
+                .add_row(index, self.all_columns.iter().cloned());
         } else {
-            self.formatted_rows
-                .get_mut(*target_index)
-                .expect("Row not found")
-                .selected_columns
-                .insert(column_name.clone());
+            self.selection.add_row(index, [column_name.clone()]);
         }
-
-        self.active_rows.insert(id);
     }
 
-    pub(crate) fn select_dragged_row_cell(
-        &mut self,
-        id: i64,
-        column_name: &F,
-        is_ctrl_pressed: bool,
-    ) {
+    pub(crate) fn select_dragged_row_cell(&mut self, id: i64, column_name: &F) {
         // If both same then the mouse is still on the same column on the same row so nothing to process
         if self.last_active_row == Some(id) && self.last_active_column == Some(column_name.clone())
         {
             return;
         }
 
-        if self.formatted_rows.is_empty() {
+        let Some((drag_start_id, drag_start_column)) = self.drag_started_on.clone() else {
             return;
-        }
+        };
+        let (Some(current_index), Some(drag_start_index)) = (
+            self.formatted_rows.index_of(id),
+            self.formatted_rows.index_of(drag_start_id),
+        ) else {
+            return;
+        };
 
-        self.active_columns.insert(column_name.clone());
+        self.last_active_row = Some(id);
+        self.last_active_column = Some(column_name.clone());
         self.beyond_drag_point = true;
-
-        let drag_start = self.drag_started_on.clone().expect("Drag start not found");
-
-        // number of the column of drag starting point and the current cell that we are trying to select
-        let drag_start_num = self.column_to_num(&drag_start.1);
-        let ongoing_column_num = self.column_to_num(column_name);
-
-        let mut new_column_set = HashSet::new();
-
-        let get_previous = ongoing_column_num > drag_start_num;
-        let mut ongoing_val = Some(drag_start.1.clone());
+        self.mark_selection_changed();
 
         // row1: column(drag started here) column column
         // row2: column                    column column
         // row3: column                    column column
         // row4: column                    column column (currently here)
         //
-        // The goal of this is to ensure from the drag starting point to all the columns till the currently here
-        // are considered selected and the rest are removed from active selection even if it was considered active
-        //
-        // During fast mouse movement active rows can contain columns that are not in the range we are targeting
-        // We go from one point to the other point and ensure except those columns nothing else is selected
-        //
-        // No active row removal if ctrl is being pressed!
-        if is_ctrl_pressed {
-            self.active_columns.insert(column_name.clone());
-        } else if ongoing_column_num == drag_start_num {
-            new_column_set.insert(drag_start.1.clone());
-            self.active_columns = new_column_set;
+        // Everything in the rectangle from the drag starting point to the current cell is
+        // selected, on top of whatever was selected when the drag started. Rebuilding it from the
+        // start of the drag on every move ensures that fast mouse movement cannot leave cells
+        // behind and moving backwards unselects the cells outside of the rectangle.
+        let columns = if self.select_full_row {
+            self.all_columns.iter().cloned().collect()
         } else {
-            while let Some(col) = ongoing_val {
-                let next_column = if get_previous {
-                    self.next_column(&col)
-                } else {
-                    self.previous_column(&col)
-                };
-
-                new_column_set.insert(col);
-
-                if &next_column == column_name {
-                    new_column_set.insert(next_column);
-                    ongoing_val = None;
-                } else {
-                    ongoing_val = Some(next_column);
-                }
-            }
-            self.active_columns = new_column_set;
-        }
+            self.column_range(&drag_start_column, column_name)
+        };
 
-        let current_row_index = self
-            .indexed_ids
-            .get(&id)
-            .expect("Current row index not found");
-        // The row the mouse pointer is on
-        let current_row = self
-            .formatted_rows
-            .get_mut(*current_row_index)
-            .expect("Current row not found");
-
-        // If this row already selects the column that we are trying to select, it means the mouse
-        // moved backwards from an active column to another active column.
-        //
-        // Row: column1 column2 (mouse is here) column3 column4
-        //
-        // In this case, if column 3 or 4 is also found in the active selection then
-        // the mouse moved backwards
-        let row_contains_column = current_row.selected_columns.contains(column_name);
-
-        let mut no_checking = false;
-        // If we have some data of the last row and column that the mouse was on, then try to unselect
-        if row_contains_column
-            && self.last_active_row.is_some()
-            && self.last_active_column.is_some()
-        {
-            if let (Some(last_active_column), Some(last_active_row)) =
-                (self.last_active_column.clone(), self.last_active_row)
-            {
-                // Remove the last column selection from the current row where the mouse is if
-                // the previous row and the current one matches
-                //
-                // column column column
-                // column column column
-                // column column (mouse is currently here) column(mouse was here)
-                //
-                // We unselect the bottom right corner column
-                if &last_active_column != column_name && last_active_row == id {
-                    current_row.selected_columns.remove(&last_active_column);
-                    self.active_columns.remove(&last_active_column);
-                }
-
-                // Get the last row where the mouse was
-                let last_row_index = self
-                    .indexed_ids
-                    .get(&last_active_row)
-                    .expect("Last row not found");
-                let last_row = self
-                    .formatted_rows
-                    .get_mut(*last_row_index)
-                    .expect("Last row not found");
-
-                self.last_active_row = Some(id);
-
-                // If on the same row as the last row, then unselect the column from all other select row
-                if id == last_row.id {
-                    if &last_active_column != column_name {
-                        self.last_active_column = Some(column_name.clone());
-                    }
-                } else {
-                    no_checking = true;
-                    // Mouse went 1 row above or below. So just clear all selection from that previous row
-                    last_row.selected_columns.clear();
-                }
-            }
+        let (first_index, last_index) = if drag_start_index <= current_index {
+            (drag_start_index, current_index)
         } else {
-            // We are in a new row which we have not selected before
-            self.active_rows.insert(current_row.id);
-            self.last_active_row = Some(id);
-            self.last_active_column = Some(column_name.clone());
-            current_row
-                .selected_columns
-                .clone_from(&self.active_columns);
-        }
+            (current_index, drag_start_index)
+        };
 
-        let current_row_index = self
-            .indexed_ids
-            .get(&id)
-            .expect("Current row index not found")
-            .to_owned();
-
-        // Get the row number where the drag started on
-        let drag_start_index = self
-            .indexed_ids
-            .get(&drag_start.0)
-            .expect("Could not find drag start")
-            .to_owned();
-
-        if !no_checking {
-            // If drag started on row 1, currently on row 5, check from row 4 to 1 and select all columns
-            // else go through all rows till a row without any selected column is found. Applied both by incrementing or decrementing index.
-            // In case of fast mouse movement following drag started point mitigates the risk of some rows not getting selected
-            self.check_row_selection(true, current_row_index, drag_start_index);
-            self.check_row_selection(false, current_row_index, drag_start_index);
-        }
-        self.remove_row_selection(current_row_index, drag_start_index, is_ctrl_pressed);
+        self.selection = self.drag_base.clone().unwrap_or_default();
+        self.selection.add_range(first_index, last_index, &columns);
     }
 
-    fn check_row_selection(&mut self, check_previous: bool, index: usize, drag_start: usize) {
-        if index == 0 && check_previous {
-            return;
-        }
+    /// All the columns from one column to the other, both included
+    pub(crate) fn column_range(&self, from: &F, to: &F) -> HashSet<F> {
+        let from_num = self.column_to_num(from);
+        let to_num = self.column_to_num(to);
 
-        if index + 1 == self.formatted_rows.len() && !check_previous {
-            return;
-        }
+        let (start, end) = if from_num <= to_num {
+            (from_num, to_num)
+        } else {
+            (to_num, from_num)
+        };
+        self.all_columns[start..=end].iter().cloned().collect()
+    }
 
-        let index = if check_previous { index - 1 } else { index + 1 };
+    /// Select all the cells in the rectangle between the two cells, both included. Replaces the
+    /// current selection.
+    pub(crate) fn select_cell_range(&mut self, start: &(i64, F), end: &(i64, F)) {
+        self.unselect_all();
 
-        let current_row = self
-            .formatted_rows
-            .get(index)
-            .expect("Current row not found");
+        let (Some(start_index), Some(end_index)) = (
+            self.formatted_rows.index_of(start.0),
+            self.formatted_rows.index_of(end.0),
+        ) else {
+            return;
+        };
 
-        // if for example drag started on row 5 and ended on row 10 but missed drag on row 7
-        // Mark the rows as selected till the drag start row is hit (if recursively going that way)
-        let unselected_row = if (check_previous && index >= drag_start)
-            || (!check_previous && index <= drag_start)
-        {
-            false
+        let selected_columns = if self.select_full_row {
+            self.all_columns.iter().cloned().collect()
         } else {
-            current_row.selected_columns.is_empty()
+            self.column_range(&start.1, &end.1)
         };
 
-        let target_row = self
-            .formatted_rows
-            .get_mut(index)
-            .expect("Target row not found");
+        let (first_index, last_index) = if start_index <= end_index {
+            (start_index, end_index)
+        } else {
+            (end_index, start_index)
+        };
 
-        if !unselected_row {
-            if self.select_full_row {
-                target_row.selected_columns.extend(self.all_columns.clone());
-            } else {
-                target_row.selected_columns.clone_from(&self.active_columns);
-            }
-            self.active_rows.insert(target_row.id);
-
-            if check_previous {
-                if index != 0 {
-                    self.check_row_selection(check_previous, index, drag_start);
-                }
-            } else if index + 1 != self.formatted_rows.len() {
-                self.check_row_selection(check_previous, index, drag_start);
-            }
+        self.mark_selection_changed();
+        self.selection
+            .add_range(first_index, last_index, &selected_columns);
+    }
+
+    /// Whether the row has at least one selected cell
+    pub(crate) fn is_row_selected(&self, id: i64) -> bool {
+        self.formatted_rows
+            .index_of(id)
+            .is_some_and(|index| self.selection.is_row_selected(index))
+    }
+
+    /// Whether the cell is selected.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    /// - `column`: The column of the cell.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if the cell is selected.
+    pub fn is_cell_selected(&self, id: i64, column: &F) -> bool {
+        self.formatted_rows
+            .index_of(id)
+            .is_some_and(|index| self.selection.is_selected(index, column))
+    }
+
+    /// Returns the selected cells of the displayed rows, keyed by the display index of the rows.
+    ///
+    /// # Returns:
+    /// - `&Selection<F>`: The current selection.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let first_row_selected = table.selection().is_row_selected(0);
+    /// ```
+    pub const fn selection(&self) -> &Selection<F> {
+        &self.selection
+    }
+
+    /// The display index and the selected columns of each selected row, in display order
+    pub(crate) fn selected_rows(&self) -> impl Iterator<Item = (usize, &HashSet<F>)> {
+        self.selection.selected_rows(self.formatted_rows.len())
+    }
+
+    /// Removes the selection from the displayed rows and returns it keyed by the row IDs, so it
+    /// can be restored after the displayed rows change
+    pub(crate) fn take_selection(&mut self) -> TakenSelection<F> {
+        let selection = std::mem::take(&mut self.selection).to_ids(&self.formatted_rows);
+        let drag_base = self
+            .drag_base
+            .take()
+            .map(|drag_base| drag_base.to_ids(&self.formatted_rows));
+
+        TakenSelection {
+            selection,
+            drag_base,
         }
     }
 
-    fn remove_row_selection(
-        &mut self,
-        current_index: usize,
-        drag_start: usize,
-        is_ctrl_pressed: bool,
-    ) {
-        let active_ids = self.active_rows.clone();
-        for id in active_ids {
-            let ongoing_index = self
-                .indexed_ids
-                .get(&id)
-                .expect("Could not get ongoing index")
-                .to_owned();
-            let target_row = self
-                .formatted_rows
-                .get_mut(ongoing_index)
-                .expect("target row not found");
-
-            if current_index > drag_start {
-                if ongoing_index >= drag_start && ongoing_index <= current_index {
-                    if self.select_full_row {
-                        target_row.selected_columns.extend(self.all_columns.clone());
-                    } else {
-                        target_row.selected_columns.clone_from(&self.active_columns);
-                    }
-                } else if !is_ctrl_pressed {
-                    target_row.selected_columns.clear();
-                    self.active_rows.remove(&target_row.id);
-                }
-            } else if ongoing_index <= drag_start && ongoing_index >= current_index {
-                if self.select_full_row {
-                    target_row.selected_columns.extend(self.all_columns.clone());
-                } else {
-                    target_row.selected_columns.clone_from(&self.active_columns);
-                }
-            } else if !is_ctrl_pressed {
-                target_row.selected_columns.clear();
-                self.active_rows.remove(&target_row.id);
+    /// Applies a selection taken with `take_selection` to the current displayed rows. Rows that
+    /// are no longer displayed are dropped from the selection, along with any drag state pointing
+    /// to them.
+    pub(crate) fn restore_selection(&mut self, taken: TakenSelection<F>) {
+        self.selection = taken.selection.into_selection(&self.formatted_rows);
+        self.drag_base = taken
+            .drag_base
+            .map(|drag_base| drag_base.into_selection(&self.formatted_rows));
+
+        self.clear_stale_cursor_state();
+    }
+
+    /// Drops the drag, focus and anchor state that points to rows that are no longer displayed
+    pub(crate) fn clear_stale_cursor_state(&mut self) {
+        if let Some((id, _)) = &self.drag_started_on {
+            if !self.formatted_rows.contains(*id) {
+                self.drag_started_on = None;
+                self.drag_base = None;
+                self.beyond_drag_point = false;
+            }
+        }
+        if let Some(id) = &self.last_active_row {
+            if !self.formatted_rows.contains(*id) {
+                self.last_active_row = None;
+                self.last_active_column = None;
+            }
+        }
+        if let Some((id, _)) = &self.focused_cell {
+            if !self.formatted_rows.contains(*id) {
+                self.focused_cell = None;
+            }
+        }
+        if let Some((id, _)) = &self.selection_anchor {
+            if !self.formatted_rows.contains(*id) {
+                self.selection_anchor = None;
+            }
+        }
+        if let Some((id, _)) = self.editing_cell() {
+            if !self.formatted_rows.contains(id) {
+                self.editing_cell = None;
             }
         }
     }
@@ -305,56 +235,42 @@ where
     /// Clears the selection in both rows and columns, and resets internal tracking of active rows
     /// and columns. After this call, there will be no selected rows or columns in the table.
     ///
-    /// # Panics:
-    /// This method will panic if the indexed ID or the corresponding row cannot be found.
-    ///
     /// # Example:
     /// ```rust,ignore
     /// table.unselect_all(); // Unselects everything in the table.
     /// ```
     pub fn unselect_all(&mut self) {
-        for id in &self.active_rows {
-            let id_index = self.indexed_ids.get(id).expect("Could not get id index");
-            let target_row = self
-                .formatted_rows
-                .get_mut(*id_index)
-                .expect("Could not get row");
-            target_row.selected_columns.clear();
+        if !self.selection.is_empty() {
+            self.mark_selection_changed();
         }
-        self.active_columns.clear();
+        self.selection.clear();
         self.last_active_row = None;
         self.last_active_column = None;
-        self.active_rows.clear();
     }
 
     /// Selects all rows and columns in the table.
     ///
-    /// After calling this method, all rows will have all columns selected.
+    /// After calling this method, all rows will have all visible columns selected. This takes the
+    /// same time no matter how many rows are displayed, and rows displayed later are selected as
+    /// well until the selection changes.
     ///
     /// # Example:
     /// ```rust,ignore
     /// table.select_all(); // Selects all rows and columns.
     /// ```
     pub fn select_all(&mut self) {
-        let mut all_rows = Vec::new();
-
-        for row in &mut self.formatted_rows {
-            row.selected_columns.extend(self.all_columns.clone());
-            all_rows.push(row.id);
-        }
+        self.mark_selection_changed();
+        let visible_columns = self.visible_columns().into_iter().collect();
 
-        self.active_columns.extend(self.all_columns.clone());
-        self.active_rows.extend(all_rows);
+        self.selection.select_all(visible_columns);
         self.last_active_row = None;
         self.last_active_column = None;
     }
 
     /// Retrieves the currently selected rows.
     ///
-    /// This method returns a vector of the rows that have one or more columns selected.
-    ///
-    /// If the `select_full_row` flag is enabled, it will ensure that all columns are selected for
-    /// each active row.
+    /// This method returns a vector of the rows that have one or more columns selected, in the
+    /// order they are displayed.
     ///
     /// # Returns:
     /// A `Vec` of `SelectableRow` instances that are currently selected.
@@ -363,31 +279,19 @@ where
     /// ```rust,ignore
     /// let selected_rows = table.get_selected_rows();
     /// ```
-    pub fn get_selected_rows(&mut self) -> Vec<SelectableRow<Row, F>> {
-        let mut selected_rows = Vec::new();
-        if self.select_full_row {
-            self.active_columns.extend(self.all_columns.clone());
-        }
-
-        // Cannot use active rows to iter as that does not maintain any proper format
-        for row in &self.formatted_rows {
-            if row.selected_columns.is_empty() {
-                continue;
-            }
-            selected_rows.push(row.clone());
-
-            // We already got all the active rows if this matches
-            if selected_rows.len() == self.active_rows.len() {
-                break;
-            }
-        }
-        selected_rows
+    pub fn get_selected_rows(&self) -> Vec<SelectableRow<Row>> {
+        self.selected_rows()
+            .filter_map(|(index, _)| self.formatted_rows.id_at(index))
+            .filter_map(|id| self.rows.get(&id))
+            .map(|row| row.as_ref().clone())
+            .collect()
     }
 
     /// Copies selected cells to the system clipboard in a tabular format.
     ///
-    /// This method copies only the selected cells from each row to the clipboard, and ensures
-    /// that the column widths align for better readability when pasted into a text editor or spreadsheet.
+    /// This method copies only the selected cells from each row to the clipboard, using the format
+    /// set with [`copy_format`](#method.copy_format). By default the column widths are aligned for
+    /// better readability when pasted into a text editor.
     ///
     /// # Parameters:
     /// - `ui`: The UI context used for clipboard interaction.
@@ -397,76 +301,46 @@ where
     /// table.copy_selected_cells(&mut ui);
     /// ```
     pub fn copy_selected_cells(&mut self, ui: &mut Ui) {
-        let mut selected_rows = Vec::new();
-        if self.select_full_row {
-            self.active_columns.extend(self.all_columns.clone());
-        }
-
-        let mut column_max_length = HashMap::new();
+        // Hidden columns are not copied
+        let selected_columns = self.selection.columns();
+        let columns: Vec<F> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|column| self.select_full_row || selected_columns.contains(column))
+            .collect();
+
+        // The rows are copied in the same order as they are shown in the UI
+        let copied_rows: Vec<Vec<String>> = self
+            .selected_rows()
+            .filter_map(|(index, row_columns)| {
+                let row = self.rows.get(&self.formatted_rows.id_at(index)?)?;
+                let cells = columns
+                    .iter()
+                    .map(|column| {
+                        if self.select_full_row || row_columns.contains(column) {
+                            column.column_text(&row.row_data)
+                        } else {
+                            String::new()
+                        }
+                    })
+                    .collect();
+                Some(cells)
+            })
+            .collect();
+
+        let headers: Option<Vec<String>> = self.copy_headers.then(|| {
+            columns
+                .iter()
+                .map(|column| self.column_display_name(column))
+                .collect()
+        });
+
+        let to_copy = self
+            .copy_format
+            .format_cells(headers.as_deref(), &copied_rows);
 
-        // Iter through all the rows and find the rows that have at least one column as selected
-        // Keep track of the biggest length of a value of a column
-        // active rows cannot be used here because hashset does not maintain an order.
-        // So itering will give the rows in a different order than what is shown in the ui
-        for row in &self.formatted_rows {
-            if row.selected_columns.is_empty() {
-                continue;
-            }
-
-            for column in &self.active_columns {
-                if row.selected_columns.contains(column) {
-                    let column_text = column.column_text(&row.row_data);
-                    let field_length = column_text.len();
-                    let entry = column_max_length.entry(column).or_insert(0);
-                    if field_length > *entry {
-                        column_max_length.insert(column, field_length);
-                    }
-                }
-            }
-            selected_rows.push(row);
-            // We already got all the active rows if this matches
-            if selected_rows.len() == self.active_rows.len() {
-                break;
-            }
-        }
-
-        let mut to_copy = String::new();
-
-        // Target is to ensure a fixed length after each column value of a row
-        // If for example highest len is 10 but the current row's
-        // column value is 5, we will add the column value and add 5 more space after that
-        // to ensure alignment
-        for row in selected_rows {
-            let mut ongoing_column = self.first_column();
-            let mut row_text = String::new();
-            loop {
-                if self.active_columns.contains(&ongoing_column)
-                    && row.selected_columns.contains(&ongoing_column)
-                {
-                    let column_text = ongoing_column.column_text(&row.row_data);
-                    row_text += &format!(
-                        "{:<width$}",
-                        column_text,
-                        width = column_max_length[&ongoing_column] + 1
-                    );
-                } else if self.active_columns.contains(&ongoing_column)
-                    && !row.selected_columns.contains(&ongoing_column)
-                {
-                    row_text += &format!(
-                        "{:<width$}",
-                        "",
-                        width = column_max_length[&ongoing_column] + 1
-                    );
-                }
-                if self.last_column() == ongoing_column {
-                    break;
-                }
-                ongoing_column = self.next_column(&ongoing_column);
-            }
-            to_copy.push_str(&row_text);
-            to_copy.push('\n');
-        }
         ui.ctx().copy_text(to_copy);
+        self.push_event(TableEvent::CopyPerformed);
     }
 
     /// Enables the selection of full rows in the table.
@@ -0,0 +1,145 @@
+use egui::ahash::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::SelectableRow;
+
+/// Mutable access to the stored rows, given to the closures of
+/// [`add_modify_row`](crate::SelectableTable::add_modify_row) and
+/// [`modify_shown_row`](crate::SelectableTable::modify_shown_row).
+///
+/// The rows are shared with the cells being drawn and the background rebuilds, so a row is only
+/// copied if it gets modified while it is shared. Rows removed here are also removed from the
+/// displayed rows once the closure returns.
+///
+/// The data of each row is copied the first time it is modified or removed, so the change can be
+/// recorded in the undo history and the key index once the closure returns.
+pub struct RowsMut<'a, Row>
+where
+    Row: Clone + Send + Sync,
+{
+    /// The rows of the table
+    rows: &'a mut HashMap<i64, Arc<SelectableRow<Row>>>,
+    /// The IDs of the rows given out for modification
+    modified: HashSet<i64>,
+    /// The rows removed by the closure
+    removed: Vec<Arc<SelectableRow<Row>>>,
+    /// The data of the modified and removed rows before their first change, if it is kept
+    before: Option<HashMap<i64, Row>>,
+}
+
+/// The changes made through [`RowsMut`]
+pub struct RowsChanges<Row>
+where
+    Row: Clone + Send + Sync,
+{
+    /// The IDs of the modified rows that still exist
+    pub modified: HashSet<i64>,
+    /// The removed rows
+    pub removed: Vec<Arc<SelectableRow<Row>>>,
+    /// The data of the modified and removed rows before their first change, if it was kept
+    pub before: HashMap<i64, Row>,
+}
+
+impl<'a, Row> RowsMut<'a, Row>
+where
+    Row: Clone + Send + Sync,
+{
+    /// Give access to the rows until the changes are taken with `into_changes`. With
+    /// `keep_before`, the data of the rows before their first change is kept.
+    pub(crate) fn new(
+        rows: &'a mut HashMap<i64, Arc<SelectableRow<Row>>>,
+        keep_before: bool,
+    ) -> Self {
+        Self {
+            rows,
+            modified: HashSet::default(),
+            removed: Vec::new(),
+            before: keep_before.then(HashMap::default),
+        }
+    }
+
+    /// The changes made to the rows
+    pub(crate) fn into_changes(self) -> RowsChanges<Row> {
+        RowsChanges {
+            modified: self.modified,
+            removed: self.removed,
+            before: self.before.unwrap_or_default(),
+        }
+    }
+
+    /// Returns the row with the ID.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `Option<&SelectableRow<Row>>`: The row, if a row with the ID exists.
+    #[must_use]
+    pub fn get(&self, id: &i64) -> Option<&SelectableRow<Row>> {
+        self.rows.get(id).map(AsRef::as_ref)
+    }
+
+    /// Returns the row with the ID for modification.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `Option<&mut SelectableRow<Row>>`: The row, if a row with the ID exists.
+    pub fn get_mut(&mut self, id: &i64) -> Option<&mut SelectableRow<Row>> {
+        let row = self.rows.get_mut(id)?;
+        if let Some(before) = self.before.as_mut() {
+            before.entry(*id).or_insert_with(|| row.row_data.clone());
+        }
+        self.modified.insert(*id);
+        Some(Arc::make_mut(row))
+    }
+
+    /// Removes the row with the ID from the table.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `Option<Row>`: The data of the removed row, if a row with the ID existed.
+    pub fn remove(&mut self, id: &i64) -> Option<Row> {
+        let row = self.rows.remove(id)?;
+        if let Some(before) = self.before.as_mut() {
+            before.entry(*id).or_insert_with(|| row.row_data.clone());
+        }
+        self.modified.remove(id);
+
+        let row_data = row.row_data.clone();
+        self.removed.push(row);
+        Some(row_data)
+    }
+
+    /// Whether a row with the ID exists.
+    ///
+    /// # Parameters:
+    /// - `id`: The ID of the row.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if the row exists.
+    #[must_use]
+    pub fn contains_key(&self, id: &i64) -> bool {
+        self.rows.contains_key(id)
+    }
+
+    /// Returns the number of rows in the table, including the ones that are not displayed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the table has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the ID and the row of every row in the table, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&i64, &SelectableRow<Row>)> {
+        self.rows.iter().map(|(id, row)| (id, row.as_ref()))
+    }
+}
@@ -0,0 +1,130 @@
+use egui::ahash::HashSet;
+use std::hash::Hash;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// Functions related to searching the rows of the table
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Search the rows and only display the ones where the text of at least one searched column
+    /// contains the query. The displayed rows are recreated immediately.
+    ///
+    /// The search is case-insensitive and is matched against [`ColumnOperations::column_text`].
+    /// By default all columns are searched, use [`set_search_columns`](#method.set_search_columns)
+    /// to limit the search to specific columns. An empty query clears the search.
+    ///
+    /// # Parameters:
+    /// - `query`: The text to look for in the rows.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_search("localhost");
+    /// let matched = table.total_displayed_rows();
+    /// let total = table.total_rows();
+    /// ```
+    pub fn set_search(&mut self, query: &str) {
+        if query.is_empty() {
+            self.search_query = None;
+        } else {
+            self.search_query = Some(query.to_string());
+        }
+        self.recreate_rows();
+    }
+
+    /// Clears the current search query and displays all rows again.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.clear_search();
+    /// ```
+    pub fn clear_search(&mut self) {
+        if self.search_query.take().is_some() {
+            self.recreate_rows();
+        }
+    }
+
+    /// Returns the currently active search query, if any.
+    ///
+    /// # Returns:
+    /// - `Option<&str>`: The search query that is being used to filter the displayed rows.
+    pub fn search_query(&self) -> Option<&str> {
+        self.search_query.as_deref()
+    }
+
+    /// Sets the columns that are checked when searching. Recreates the displayed rows if a
+    /// search is currently active.
+    ///
+    /// # Parameters:
+    /// - `columns`: The columns to search in. If `None` is provided, all columns are searched.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_search_columns(Some(vec![Column::Hostname, Column::Address]));
+    /// table.set_search_columns(None); // Search all columns
+    /// ```
+    pub fn set_search_columns(&mut self, columns: Option<Vec<F>>) {
+        self.search_columns = columns.map(|columns| columns.into_iter().collect());
+        if self.search_query.is_some() {
+            self.recreate_rows();
+        }
+    }
+
+    /// Limits the search to the given columns in a builder-style pattern.
+    ///
+    /// # Parameters:
+    /// - `columns`: The columns to search in.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table that only searches the given columns.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .search_columns(vec![col1, col3]);
+    /// ```
+    #[must_use]
+    pub fn search_columns(mut self, columns: Vec<F>) -> Self {
+        self.search_columns = Some(columns.into_iter().collect::<HashSet<F>>());
+        self
+    }
+
+    /// The columns that should be checked when searching
+    pub(crate) fn searched_columns(&self) -> Vec<F> {
+        self.search_columns.as_ref().map_or_else(
+            || self.all_columns.clone(),
+            |columns| columns.iter().cloned().collect(),
+        )
+    }
+}
+
+/// Whether the text of any of the given columns of the row contains the lowercased query
+pub fn row_matches_search<Row, F, Conf>(row: &Row, query: &str, columns: &[F]) -> bool
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    columns
+        .iter()
+        .any(|column| column.column_text(row).to_lowercase().contains(query))
+}
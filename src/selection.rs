@@ -0,0 +1,620 @@
+use egui::ahash::{HashMap, HashSet};
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::iter::Peekable;
+
+use crate::DisplayedRows;
+
+/// The selected cells of the displayed rows, keyed by the display index of the rows.
+///
+/// The selection is made of three layers. Rows with their own column set come first, then
+/// rectangular ranges of rows sharing a column set and finally the column set of every displayed
+/// row, used when everything is selected. Checking a cell is `O(log n)` and selecting everything
+/// is `O(1)`, no matter how many rows are displayed.
+#[derive(Clone)]
+pub struct Selection<F>
+where
+    F: Eq + Hash + Clone,
+{
+    /// The selected columns of every displayed row, set by selecting everything
+    all: Option<HashSet<F>>,
+    /// Non-overlapping ranges of rows, from the first row index to the last row index and the
+    /// columns selected in each of them
+    ranges: BTreeMap<usize, (usize, HashSet<F>)>,
+    /// Rows whose selected columns differ from their range or from `all`. An empty set unselects
+    /// the row.
+    rows: BTreeMap<usize, HashSet<F>>,
+}
+
+impl<F> Default for Selection<F>
+where
+    F: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self {
+            all: None,
+            ranges: BTreeMap::new(),
+            rows: BTreeMap::new(),
+        }
+    }
+}
+
+impl<F> Selection<F>
+where
+    F: Eq + Hash + Clone,
+{
+    /// Returns the selected columns of the row at the display index.
+    ///
+    /// # Parameters:
+    /// - `index`: The display index of the row.
+    ///
+    /// # Returns:
+    /// - `Option<&HashSet<F>>`: The selected columns, `None` or an empty set if the row is not
+    ///   selected.
+    #[must_use]
+    pub fn columns_at(&self, index: usize) -> Option<&HashSet<F>> {
+        if let Some(columns) = self.rows.get(&index) {
+            return Some(columns);
+        }
+
+        if let Some((_, (last, columns))) = self.ranges.range(..=index).next_back() {
+            if *last >= index {
+                return Some(columns);
+            }
+        }
+
+        self.all.as_ref()
+    }
+
+    /// Whether the cell of the row at the display index is selected.
+    ///
+    /// # Parameters:
+    /// - `index`: The display index of the row.
+    /// - `column`: The column of the cell.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if the cell is selected.
+    #[must_use]
+    pub fn is_selected(&self, index: usize, column: &F) -> bool {
+        self.columns_at(index)
+            .is_some_and(|columns| columns.contains(column))
+    }
+
+    /// Whether the row at the display index has at least one selected cell.
+    ///
+    /// # Parameters:
+    /// - `index`: The display index of the row.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if the row is selected.
+    #[must_use]
+    pub fn is_row_selected(&self, index: usize) -> bool {
+        self.columns_at(index)
+            .is_some_and(|columns| !columns.is_empty())
+    }
+
+    /// Whether no cell is selected.
+    ///
+    /// # Returns:
+    /// - `bool`: `true` if nothing is selected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.column_sets().all(HashSet::is_empty)
+    }
+
+    /// Returns every column that is selected in at least one row.
+    ///
+    /// # Returns:
+    /// - `HashSet<F>`: The selected columns.
+    #[must_use]
+    pub fn columns(&self) -> HashSet<F> {
+        self.column_sets().flatten().cloned().collect()
+    }
+
+    /// All the column sets of the selection
+    fn column_sets(&self) -> impl Iterator<Item = &HashSet<F>> {
+        self.all
+            .iter()
+            .chain(self.ranges.values().map(|(_, columns)| columns))
+            .chain(self.rows.values())
+    }
+
+    /// Unselect everything
+    pub(crate) fn clear(&mut self) {
+        self.all = None;
+        self.ranges.clear();
+        self.rows.clear();
+    }
+
+    /// Select the columns in every displayed row, replacing the current selection
+    pub(crate) fn select_all(&mut self, columns: HashSet<F>) {
+        self.clear();
+        self.all = Some(columns);
+    }
+
+    /// Add the columns to the selected columns of the row
+    pub(crate) fn add_row(&mut self, index: usize, columns: impl IntoIterator<Item = F>) {
+        let mut row_columns = self.columns_at(index).cloned().unwrap_or_default();
+        row_columns.extend(columns);
+        self.rows.insert(index, row_columns);
+    }
+
+    /// Add the columns to the selected columns of every row from `first` to `last`, both included.
+    /// The ranges inside are split at `first` and `last` and their columns extended, and the
+    /// rows between them are covered by new ranges, instead of going through every row.
+    pub(crate) fn add_range(&mut self, first: usize, last: usize, columns: &HashSet<F>) {
+        if columns.is_empty() || first > last {
+            return;
+        }
+
+        for row_columns in self
+            .rows
+            .range_mut(first..=last)
+            .map(|(_, columns)| columns)
+        {
+            row_columns.extend(columns.iter().cloned());
+        }
+
+        let after_last = last.checked_add(1);
+        self.split_range_at(first);
+        if let Some(after_last) = after_last {
+            self.split_range_at(after_last);
+        }
+
+        let mut inside = self.ranges.split_off(&first);
+        if let Some(after_last) = after_last {
+            let mut after = inside.split_off(&after_last);
+            self.ranges.append(&mut after);
+        }
+
+        // The rows that are in no range get the columns of `all` with the new ones
+        let mut gap_columns = self.all.clone().unwrap_or_default();
+        gap_columns.extend(columns.iter().cloned());
+
+        let mut pieces: Vec<(usize, usize, HashSet<F>)> = Vec::new();
+        let mut next_index = Some(first);
+        for (range_first, (range_last, mut range_columns)) in inside {
+            if let Some(gap_first) = next_index.filter(|index| *index < range_first) {
+                pieces.push((gap_first, range_first - 1, gap_columns.clone()));
+            }
+            range_columns.extend(columns.iter().cloned());
+            pieces.push((range_first, range_last, range_columns));
+            next_index = range_last.checked_add(1);
+        }
+        if let Some(gap_first) = next_index.filter(|index| *index <= last) {
+            pieces.push((gap_first, last, gap_columns));
+        }
+
+        for (piece_first, piece_last, piece_columns) in pieces {
+            self.ranges.insert(piece_first, (piece_last, piece_columns));
+            self.merge_ranges_at(piece_first);
+        }
+        if let Some(after_last) = after_last {
+            self.merge_ranges_at(after_last);
+        }
+    }
+
+    /// Split the range going over the index so that one of its parts starts at the index
+    fn split_range_at(&mut self, index: usize) {
+        let split_range = self
+            .ranges
+            .range(..index)
+            .next_back()
+            .filter(|(_, (last, _))| *last >= index)
+            .map(|(first, (last, columns))| (*first, *last, columns.clone()));
+        if let Some((first, last, columns)) = split_range {
+            self.ranges.insert(index, (last, columns.clone()));
+            self.ranges.insert(first, (index - 1, columns));
+        }
+    }
+
+    /// Merge the range starting at the index into the range ending right before it if both have
+    /// the same columns
+    fn merge_ranges_at(&mut self, index: usize) {
+        let Some(previous_last) = index.checked_sub(1) else {
+            return;
+        };
+        let Some((previous_first, (range_last, range_columns))) =
+            self.ranges.range(..index).next_back()
+        else {
+            return;
+        };
+        if *range_last != previous_last {
+            return;
+        }
+        let previous_first = *previous_first;
+
+        let same_columns = self
+            .ranges
+            .get(&index)
+            .is_some_and(|(_, columns)| columns == range_columns);
+        if !same_columns {
+            return;
+        }
+
+        if let Some((last, _)) = self.ranges.remove(&index) {
+            if let Some((previous_last, _)) = self.ranges.get_mut(&previous_first) {
+                *previous_last = last;
+            }
+        }
+    }
+
+    /// Remove the row at the display index, moving the rows after it up by one
+    pub(crate) fn remove_index(&mut self, index: usize) {
+        self.rows.remove(&index);
+        let shifted_rows = self.rows.split_off(&index);
+        self.rows.extend(
+            shifted_rows
+                .into_iter()
+                .map(|(row_index, columns)| (row_index - 1, columns)),
+        );
+
+        let shifted_ranges = self.ranges.split_off(&index);
+        if let Some((_, (last, _))) = self.ranges.range_mut(..index).next_back() {
+            if *last >= index {
+                *last -= 1;
+            }
+        }
+        for (first, (last, columns)) in shifted_ranges {
+            if first == index {
+                // A range of only the removed row is gone
+                if last > index {
+                    self.ranges.insert(first, (last - 1, columns));
+                }
+            } else {
+                self.ranges.insert(first - 1, (last - 1, columns));
+            }
+        }
+    }
+
+    /// Insert a row at the display index, moving the rows from the index down by one. The row
+    /// gets the given columns, or is only selected by `all` if there are none.
+    pub(crate) fn insert_index(&mut self, index: usize, columns: Option<HashSet<F>>) {
+        let shifted_rows = self.rows.split_off(&index);
+        self.rows.extend(
+            shifted_rows
+                .into_iter()
+                .map(|(row_index, columns)| (row_index + 1, columns)),
+        );
+
+        // A range going over the index is split around the new row
+        self.split_range_at(index);
+
+        let shifted_ranges = self.ranges.split_off(&index);
+        self.ranges.extend(
+            shifted_ranges
+                .into_iter()
+                .map(|(first, (last, columns))| (first + 1, (last + 1, columns))),
+        );
+
+        if let Some(columns) = columns {
+            let differs = self
+                .columns_at(index)
+                .map_or(!columns.is_empty(), |current| *current != columns);
+            if differs {
+                self.rows.insert(index, columns);
+            }
+        }
+    }
+
+    /// The display index and the selected columns of each selected row, in display order. Only
+    /// rows before `len` are included.
+    pub(crate) fn selected_rows(
+        &self,
+        len: usize,
+    ) -> Box<dyn Iterator<Item = (usize, &HashSet<F>)> + '_> {
+        let selected = move |index: usize| {
+            self.columns_at(index)
+                .filter(|columns| !columns.is_empty())
+                .map(|columns| (index, columns))
+        };
+
+        if self.all.is_some() {
+            Box::new((0..len).filter_map(selected))
+        } else {
+            Box::new(
+                self.listed_indices()
+                    .take_while(move |index| *index < len)
+                    .filter_map(selected),
+            )
+        }
+    }
+
+    /// The indices of the rows that are in a range or have their own columns, in order
+    fn listed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        let range_indices = self
+            .ranges
+            .iter()
+            .flat_map(|(first, (last, _))| *first..=*last);
+
+        MergedIndices {
+            left: range_indices.peekable(),
+            right: self.rows.keys().copied().peekable(),
+        }
+    }
+
+    /// Convert the selection to the row IDs so it can be applied after the displayed rows change
+    pub(crate) fn to_ids(&self, displayed_rows: &DisplayedRows) -> IdSelection<F> {
+        let mut column_sets = Vec::new();
+        let mut range_sets = HashMap::default();
+        let mut rows = Vec::new();
+        let mut previous: Option<(usize, usize)> = None;
+
+        for index in self.listed_indices() {
+            let Some(id) = displayed_rows.id_at(index) else {
+                break;
+            };
+
+            let set_index = if let Some(columns) = self.rows.get(&index) {
+                // Unselected rows only matter if they are an exception to `all`
+                if columns.is_empty() && self.all.is_none() {
+                    continue;
+                }
+                // Neighbouring rows with the same columns share them, so they become a range
+                match previous {
+                    Some((previous_index, set_index))
+                        if previous_index + 1 == index && column_sets[set_index] == *columns =>
+                    {
+                        set_index
+                    }
+                    _ => {
+                        column_sets.push(columns.clone());
+                        column_sets.len() - 1
+                    }
+                }
+            } else if let Some((first, (_, columns))) = self.ranges.range(..=index).next_back() {
+                // The rows of a range share a single copy of its columns
+                *range_sets.entry(*first).or_insert_with(|| {
+                    column_sets.push(columns.clone());
+                    column_sets.len() - 1
+                })
+            } else {
+                continue;
+            };
+            rows.push((id, set_index));
+            previous = Some((index, set_index));
+        }
+
+        IdSelection {
+            all: self.all.clone(),
+            column_sets,
+            rows,
+        }
+    }
+}
+
+/// A selection keyed by the row IDs instead of the display index
+pub struct IdSelection<F> {
+    /// The selected columns of every displayed row, set by selecting everything
+    all: Option<HashSet<F>>,
+    /// The column sets shared by the rows
+    column_sets: Vec<HashSet<F>>,
+    /// The ID of each row with its own selected columns and the index of its column set
+    rows: Vec<(i64, usize)>,
+}
+
+impl<F> IdSelection<F>
+where
+    F: Eq + Hash + Clone,
+{
+    /// Convert the selection back to the display indices. Rows that are no longer displayed are
+    /// dropped and neighbouring rows sharing their columns are merged into ranges.
+    pub(crate) fn into_selection(self, displayed_rows: &DisplayedRows) -> Selection<F> {
+        let mut indexed_rows: Vec<(usize, usize)> = self
+            .rows
+            .into_iter()
+            .filter_map(|(id, set_index)| Some((displayed_rows.index_of(id)?, set_index)))
+            .collect();
+        indexed_rows.sort_unstable();
+
+        let mut selection = Selection {
+            all: self.all,
+            ..Selection::default()
+        };
+
+        let mut indexed_rows = indexed_rows.into_iter().peekable();
+        while let Some((first, set_index)) = indexed_rows.next() {
+            let mut last = first;
+            while let Some(&(index, next_set_index)) = indexed_rows.peek() {
+                if index != last + 1 || next_set_index != set_index {
+                    break;
+                }
+                last = index;
+                indexed_rows.next();
+            }
+            selection
+                .ranges
+                .insert(first, (last, self.column_sets[set_index].clone()));
+        }
+        selection
+    }
+}
+
+/// Merges two ascending iterators of indices into one, without duplicates
+struct MergedIndices<L, R>
+where
+    L: Iterator<Item = usize>,
+    R: Iterator<Item = usize>,
+{
+    left: Peekable<L>,
+    right: Peekable<R>,
+}
+
+impl<L, R> Iterator for MergedIndices<L, R>
+where
+    L: Iterator<Item = usize>,
+    R: Iterator<Item = usize>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match (self.left.peek(), self.right.peek()) {
+            (Some(left), Some(right)) if left == right => {
+                self.right.next();
+                self.left.next()
+            }
+            (Some(left), Some(right)) if left < right => self.left.next(),
+            (_, Some(_)) => self.right.next(),
+            _ => self.left.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(columns: &[u8]) -> HashSet<u8> {
+        columns.iter().copied().collect()
+    }
+
+    fn range_selection(first: usize, last: usize) -> Selection<u8> {
+        let mut selection = Selection::default();
+        selection.add_range(first, last, &set(&[1]));
+        selection
+    }
+
+    fn selected_indices(selection: &Selection<u8>, len: usize) -> Vec<usize> {
+        selection
+            .selected_rows(len)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn remove_inside_range_shrinks_it() {
+        let mut selection = range_selection(2, 5);
+        selection.remove_index(3);
+        assert_eq!(selected_indices(&selection, 10), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn remove_at_range_start_shrinks_it() {
+        let mut selection = range_selection(2, 5);
+        selection.remove_index(2);
+        assert_eq!(selected_indices(&selection, 10), vec![2, 3, 4]);
+
+        let mut selection = range_selection(2, 2);
+        selection.remove_index(2);
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn remove_after_range_keeps_it() {
+        let mut selection = range_selection(2, 5);
+        selection.add_row(8, [2]);
+        selection.remove_index(7);
+        assert_eq!(selected_indices(&selection, 10), vec![2, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn insert_inside_range_splits_it() {
+        let mut selection = range_selection(2, 5);
+        selection.insert_index(3, None);
+        assert_eq!(selected_indices(&selection, 10), vec![2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn insert_at_range_start_moves_it() {
+        let mut selection = range_selection(2, 5);
+        selection.insert_index(2, None);
+        assert_eq!(selected_indices(&selection, 10), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn insert_after_range_keeps_it() {
+        let mut selection = range_selection(2, 5);
+        selection.insert_index(6, Some(set(&[2])));
+        assert_eq!(selected_indices(&selection, 10), vec![2, 3, 4, 5, 6]);
+        assert!(selection.is_selected(6, &2));
+        assert!(!selection.is_selected(6, &1));
+    }
+
+    #[test]
+    fn round_trip_through_ids_keeps_all_and_exceptions() {
+        let mut selection = Selection::default();
+        selection.select_all(set(&[1, 2]));
+        selection.rows.insert(1, HashSet::default());
+        selection.add_row(2, [3]);
+
+        let ids = selection.to_ids(&[10, 11, 12].into_iter().collect());
+        let selection = ids.into_selection(&[11, 12, 10].into_iter().collect());
+
+        assert!(!selection.is_row_selected(0));
+        assert_eq!(selection.columns_at(1), Some(&set(&[1, 2, 3])));
+        assert_eq!(selection.columns_at(2), Some(&set(&[1, 2])));
+        assert_eq!(selection.columns_at(3), Some(&set(&[1, 2])));
+    }
+
+    #[test]
+    fn round_trip_through_ids_merges_adjacent_rows() {
+        let mut selection = Selection::default();
+        for index in [0, 1, 2, 4] {
+            selection.add_row(index, [1]);
+        }
+
+        let ids = selection.to_ids(&[10, 11, 12, 13, 14].into_iter().collect());
+        let selection = ids.into_selection(&[10, 11, 12, 15, 14].into_iter().collect());
+
+        assert!(selection.rows.is_empty());
+        assert_eq!(selection.ranges.len(), 2);
+        assert_eq!(selected_indices(&selection, 10), vec![0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn round_trip_through_ids_drops_missing_rows() {
+        let selection = range_selection(0, 2);
+
+        let ids = selection.to_ids(&[10, 11, 12].into_iter().collect());
+        let selection = ids.into_selection(&[10, 12].into_iter().collect());
+
+        assert_eq!(selected_indices(&selection, 10), vec![0, 1]);
+    }
+
+    #[test]
+    fn add_range_splits_overlapping_ranges() {
+        let mut selection = range_selection(0, 3);
+        selection.add_row(2, [2]);
+        selection.add_range(2, 6, &set(&[3]));
+
+        assert_eq!(selection.columns_at(1), Some(&set(&[1])));
+        assert_eq!(selection.columns_at(2), Some(&set(&[1, 2, 3])));
+        assert_eq!(selection.columns_at(3), Some(&set(&[1, 3])));
+        assert_eq!(selection.columns_at(5), Some(&set(&[3])));
+        assert!(!selection.is_row_selected(7));
+        assert_eq!(selection.rows.len(), 1);
+        assert_eq!(selection.ranges.len(), 3);
+    }
+
+    #[test]
+    fn add_range_extends_all() {
+        let mut selection = Selection::default();
+        selection.select_all(set(&[1]));
+        selection.add_range(1, 2, &set(&[2]));
+
+        assert_eq!(selection.columns_at(0), Some(&set(&[1])));
+        assert_eq!(selection.columns_at(1), Some(&set(&[1, 2])));
+        assert_eq!(selection.columns_at(2), Some(&set(&[1, 2])));
+        assert_eq!(selection.columns_at(3), Some(&set(&[1])));
+    }
+
+    #[test]
+    fn add_range_merges_adjacent_ranges() {
+        let mut selection = range_selection(0, 2);
+        selection.add_range(6, 8, &set(&[1]));
+        selection.add_range(3, 5, &set(&[1]));
+
+        assert_eq!(selection.ranges.len(), 1);
+        assert_eq!(selection.ranges.get(&0), Some(&(8, set(&[1]))));
+    }
+
+    #[test]
+    fn merged_indices_skip_duplicates() {
+        let merged: Vec<usize> = MergedIndices {
+            left: [1, 3, 5].into_iter().peekable(),
+            right: [2, 3, 6].into_iter().peekable(),
+        }
+        .collect();
+        assert_eq!(merged, vec![1, 2, 3, 5, 6]);
+    }
+}
@@ -0,0 +1,245 @@
+use egui::ahash::{HashSet, HashSetExt};
+use egui::Ui;
+use std::hash::Hash;
+use std::sync::mpsc::Receiver;
+
+use crate::{ColumnOperations, ColumnOrdering, SelectableTable};
+
+/// A change to the rows of the table, sent by a data source such as a worker thread.
+pub enum TableUpdate<Row> {
+    /// Adds a new row. With [`row_keys`](SelectableTable::row_keys) enabled, a row whose key is
+    /// already used replaces the data of that row.
+    Insert(Row),
+    /// Replaces the data of the row with the ID. Ignored if no row has the ID. With row keys
+    /// enabled, an update that would give the row the key of another row is rejected with a
+    /// [`TableEvent::UpdateRejected`](crate::TableEvent::UpdateRejected).
+    Update(i64, Row),
+    /// Removes the row with the ID. Ignored if no row has the ID.
+    Remove(i64),
+    /// Removes all rows.
+    Clear,
+}
+
+/// A source of [`TableUpdate`]s that the table drains in `show_ui`.
+///
+/// Implemented for [`std::sync::mpsc::Receiver`], implement it for other channels as needed.
+pub trait UpdateSource<Row>: Send {
+    /// Returns the next pending update without blocking, or `None` if there is nothing to apply
+    /// right now.
+    fn try_next(&mut self) -> Option<TableUpdate<Row>>;
+}
+
+impl<Row> UpdateSource<Row> for Receiver<TableUpdate<Row>>
+where
+    Row: Send,
+{
+    fn try_next(&mut self) -> Option<TableUpdate<Row>> {
+        self.try_recv().ok()
+    }
+}
+
+/// The source of the updates and how many of them are applied per frame
+pub struct LiveUpdates<Row> {
+    /// Where the updates come from
+    pub source: Option<Box<dyn UpdateSource<Row>>>,
+    /// The maximum number of updates applied in a single frame
+    pub budget: usize,
+}
+
+impl<Row> Default for LiveUpdates<Row> {
+    fn default() -> Self {
+        Self {
+            source: None,
+            budget: 10_000,
+        }
+    }
+}
+
+/// Functions related to feeding the table from a data source
+impl<Row, F, Conf> SelectableTable<Row, F, Conf>
+where
+    Row: Clone + Send + Sync,
+    F: Eq
+        + Hash
+        + Clone
+        + Ord
+        + Send
+        + Sync
+        + Default
+        + ColumnOperations<Row, F, Conf>
+        + ColumnOrdering<Row>,
+    Conf: Default,
+{
+    /// Sets a source of [`TableUpdate`]s, such as the receiving end of a channel, that is drained
+    /// on every `show_ui` call. Worker threads can then feed the table without access to it.
+    ///
+    /// # Considerations:
+    /// - Updated and removed rows are changed in the UI right away. Inserted rows become visible
+    ///   once the rows are recreated, each update counting toward the
+    ///   [`auto_reload`](#method.auto_reload) count.
+    /// - The UI is only repainted on input by default, so the sender should call
+    ///   `Context::request_repaint` after sending updates.
+    ///
+    /// # Parameters:
+    /// - `source`: Where the updates come from.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table reading from the source.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .auto_reload(1000)
+    ///     .update_source(receiver);
+    ///
+    /// std::thread::spawn(move || {
+    ///     sender.send(TableUpdate::Insert(my_row)).unwrap();
+    ///     ctx.request_repaint();
+    /// });
+    /// ```
+    #[must_use]
+    pub fn update_source<S>(mut self, source: S) -> Self
+    where
+        S: UpdateSource<Row> + 'static,
+    {
+        self.live_updates.source = Some(Box::new(source));
+        self
+    }
+
+    /// Sets or removes the source of [`TableUpdate`]s drained on every `show_ui` call.
+    ///
+    /// # Parameters:
+    /// - `source`: The new source, or `None` to stop reading updates.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_update_source(Some(Box::new(receiver)));
+    /// ```
+    pub fn set_update_source(&mut self, source: Option<Box<dyn UpdateSource<Row>>>) {
+        self.live_updates.source = source;
+    }
+
+    /// Sets the maximum number of updates applied from the source in a single frame. The rest
+    /// are applied in the following frames, keeping the UI responsive. Default: 10,000
+    ///
+    /// # Parameters:
+    /// - `budget`: The maximum number of updates per frame. At least one update is always
+    ///   applied, a budget of 0 is treated as 1.
+    ///
+    /// # Returns:
+    /// - `Self`: The modified table with the new budget.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// let table = SelectableTable::new(vec![col1, col2, col3])
+    ///     .update_source(receiver)
+    ///     .update_budget(5000);
+    /// ```
+    #[must_use]
+    pub const fn update_budget(mut self, budget: usize) -> Self {
+        self.set_update_budget(budget);
+        self
+    }
+
+    /// Sets the maximum number of updates applied from the source in a single frame.
+    ///
+    /// # Parameters:
+    /// - `budget`: The maximum number of updates per frame. A budget of 0 is treated as 1.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.set_update_budget(5000);
+    /// ```
+    pub const fn set_update_budget(&mut self, budget: usize) {
+        // Nothing would ever be applied with no budget
+        self.live_updates.budget = if budget == 0 { 1 } else { budget };
+    }
+
+    /// Applies a single update to the rows, the same way as the updates coming from the source.
+    ///
+    /// # Parameters:
+    /// - `update`: The change to apply.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    /// table.apply_update(TableUpdate::Remove(row_id));
+    /// ```
+    pub fn apply_update(&mut self, update: TableUpdate<Row>) {
+        let mut removed_ids = HashSet::new();
+        let reload = self.apply_update_without_reload(update, &mut removed_ids);
+        self.remove_displayed_rows(&removed_ids);
+
+        self.commit_history();
+        if reload {
+            self.recreate_rows();
+        }
+    }
+
+    /// Apply the pending updates of the source, up to the budget
+    pub(crate) fn drain_update_source(&mut self, ui: &Ui) {
+        let Some(mut source) = self.live_updates.source.take() else {
+            return;
+        };
+
+        let mut reload = false;
+        let mut applied = 0;
+        let mut removed_ids = HashSet::new();
+
+        self.start_placement_batch();
+        while applied < self.live_updates.budget {
+            let Some(update) = source.try_next() else {
+                break;
+            };
+            reload |= self.apply_update_without_reload(update, &mut removed_ids);
+            applied += 1;
+        }
+        // The removed rows leave the displayed rows in a single pass
+        self.remove_displayed_rows(&removed_ids);
+        self.end_placement_batch();
+
+        self.live_updates.source = Some(source);
+
+        self.commit_history();
+        if reload {
+            self.recreate_rows();
+        }
+
+        // There may be more updates waiting
+        if applied == self.live_updates.budget {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Apply the update and return whether the auto reload count was reached. Updates that
+    /// change nothing are not counted. Removed rows are added to `removed_ids` and stay in the
+    /// displayed rows until the caller removes them.
+    fn apply_update_without_reload(
+        &mut self,
+        update: TableUpdate<Row>,
+        removed_ids: &mut HashSet<i64>,
+    ) -> bool {
+        match update {
+            TableUpdate::Insert(row) => {
+                self.store_row(row);
+            }
+            TableUpdate::Update(id, row) => {
+                // Updates of unknown rows change nothing
+                if !self.replace_row_data(id, row) {
+                    return false;
+                }
+            }
+            TableUpdate::Remove(id) => {
+                if self.take_stored_row(id).is_none() {
+                    return false;
+                }
+                removed_ids.insert(id);
+            }
+            TableUpdate::Clear => {
+                self.clear_all_rows();
+                return false;
+            }
+        }
+        self.auto_reload.increment_count()
+    }
+}